@@ -0,0 +1,334 @@
+use std::collections::HashSet;
+
+use robotics_lib::world::environmental_conditions::EnvironmentalConditions;
+use robotics_lib::world::tile::Tile;
+
+use crate::dijkstra::{dijkstra, search_with_blocked_edges, BlockedEdges, Edge, Graph};
+use crate::error::TomTomError;
+use crate::path::{Action, Path, StepCost};
+use crate::search::SearchOptions;
+use crate::utils::get_action_destination;
+
+/// DIVERSITY_ATTEMPTS bounds the number of paths enumerated for each requested path, when a minimum diversity is specified.
+const DIVERSITY_ATTEMPTS: usize = 20;
+
+/// Route is a path together with the tiles it visits and the actions' search distances.
+#[derive(Clone)]
+struct Route {
+    tiles: Vec<(usize, usize)>,
+    edges: Vec<Edge>,
+    step_costs: Vec<StepCost>,
+    distance: usize,
+}
+
+impl Route {
+    fn new(graph: &Graph, path: &Path, first_step: usize) -> Option<Route> {
+        let mut tiles = vec![path.source];
+        let mut edges = Vec::new();
+        let mut step_costs = Vec::new();
+        let mut node = path.source;
+        let mut buffer = Vec::new();
+
+        for (step, action) in path.actions.iter().enumerate() {
+            let destination = get_action_destination(graph.size(), node, action)?;
+
+            graph.edges(node, first_step + step, &mut buffer);
+
+            let edge = buffer.drain(..).find(|edge| {
+                edge.node == destination
+                    && matches!(
+                        (&edge.action, action),
+                        (Action::Go(_), Action::Go(_)) | (Action::Teleport(_), Action::Teleport(_))
+                    )
+            })?;
+
+            step_costs.push(graph.step_cost(node, &edge, first_step + step));
+            node = destination;
+            tiles.push(node);
+            edges.push(edge);
+        }
+
+        let distance = edges.iter().map(|edge| edge.distance).sum();
+
+        Some(Route {
+            tiles,
+            edges,
+            step_costs,
+            distance,
+        })
+    }
+
+    fn cost(&self) -> usize {
+        self.edges.iter().map(|edge| edge.cost).sum()
+    }
+
+    /// is_loopless returns whether the route visits every tile at most once.
+    fn is_loopless(&self) -> bool {
+        let mut visited = HashSet::new();
+        self.tiles.iter().all(|tile| visited.insert(*tile))
+    }
+
+    /// concat returns the route following this route up to the tile of the given index, then the spur route.
+    fn concat(&self, index: usize, spur: &Route) -> Route {
+        let mut tiles = self.tiles[..index].to_vec();
+        tiles.extend(spur.tiles.iter().copied());
+
+        let mut edges = self.edges[..index].to_vec();
+        edges.extend(spur.edges.iter().cloned());
+
+        let mut step_costs = self.step_costs[..index].to_vec();
+        step_costs.extend(spur.step_costs.iter().cloned());
+
+        let distance = edges.iter().map(|edge| edge.distance).sum();
+
+        Route {
+            tiles,
+            edges,
+            step_costs,
+            distance,
+        }
+    }
+
+    /// diversity returns the fraction of the tiles visited after the source tile that the other route doesn't visit.
+    fn diversity(&self, other: &Route) -> f64 {
+        let tiles = &self.tiles[1..];
+
+        if tiles.is_empty() {
+            return 0.0;
+        }
+
+        let other_tiles: HashSet<&(usize, usize)> = other.tiles[1..].iter().collect();
+
+        tiles
+            .iter()
+            .filter(|tile| !other_tiles.contains(tile))
+            .count() as f64
+            / tiles.len() as f64
+    }
+
+    fn path(&self) -> Path {
+        let source = self.tiles[0];
+        let destination = self.tiles[self.tiles.len() - 1];
+
+        let mut path = Path::new(source, destination, self.cost());
+        path.actions = self.edges.iter().map(|edge| edge.action.clone()).collect();
+        path.unknown_steps = self.edges.iter().filter(|edge| edge.unknown).count();
+        path.breakdown = self.step_costs.clone();
+
+        path
+    }
+}
+
+/// alternatives returns up to k loopless paths from the source tile to the targets, ordered by search distance, with Yen's algorithm.
+/// If a minimum diversity is specified, the paths visiting a smaller fraction of tiles not visited by every previous path are skipped.
+/// With a forecast, a spur path may revisit a tile when waiting for cheaper conditions pays: such spur paths are skipped.
+pub(crate) fn alternatives(
+    environmental_conditions: &EnvironmentalConditions,
+    map: &[Vec<Option<Tile>>],
+    source: (usize, usize),
+    targets: HashSet<(usize, usize)>,
+    k: usize,
+    min_diversity: Option<f64>,
+    options: &SearchOptions,
+) -> Result<Vec<Path>, TomTomError> {
+    if targets.is_empty() {
+        return Err(TomTomError::NoTargets);
+    }
+
+    if k == 0 {
+        return Ok(Vec::new());
+    }
+
+    let graph = Graph::new(map, environmental_conditions, options);
+
+    let first = dijkstra(
+        environmental_conditions,
+        map,
+        source,
+        targets.clone(),
+        options,
+    )?;
+    let first = Route::new(&graph, &first, 0).ok_or(TomTomError::Unreachable)?;
+
+    let limit = match min_diversity {
+        None => k,
+        Some(_) => k.saturating_mul(DIVERSITY_ATTEMPTS),
+    };
+
+    let mut enumerated = vec![first];
+    let mut candidates: Vec<Route> = Vec::new();
+    let mut ret: Vec<Route> = Vec::new();
+
+    loop {
+        let last = enumerated[enumerated.len() - 1].clone();
+
+        let diverse = match min_diversity {
+            None => true,
+            Some(min_diversity) => ret
+                .iter()
+                .all(|route| last.diversity(route) >= min_diversity),
+        };
+
+        if diverse {
+            ret.push(last.clone());
+        }
+
+        if ret.len() >= k || enumerated.len() >= limit {
+            break;
+        }
+
+        for index in 0..last.edges.len() {
+            let spur = last.tiles[index];
+            let root = &last.tiles[..=index];
+
+            let blocked_edges: BlockedEdges = enumerated
+                .iter()
+                .filter(|route| route.tiles.len() > index + 1 && route.tiles[..=index] == *root)
+                .map(|route| (spur, route.tiles[index + 1]))
+                .collect();
+
+            let mut spur_options = options.clone();
+            spur_options
+                .constraints
+                .excluded_coordinates
+                .extend(root[..index].iter().copied());
+
+            let spur_graph = Graph::new(map, environmental_conditions, &spur_options);
+            let tree =
+                search_with_blocked_edges(&spur_graph, spur, index, &targets, &blocked_edges)?;
+
+            let spur_route = match tree
+                .nearest_path(&spur_graph, &targets)
+                .and_then(|path| Route::new(&graph, &path, index))
+            {
+                Some(spur_route) if spur_route.is_loopless() => spur_route,
+                _ => continue,
+            };
+
+            let candidate = last.concat(index, &spur_route);
+
+            if !candidates
+                .iter()
+                .chain(enumerated.iter())
+                .any(|route| route.tiles == candidate.tiles)
+            {
+                candidates.push(candidate);
+            }
+        }
+
+        match candidates
+            .iter()
+            .enumerate()
+            .min_by_key(|(_, route)| (route.distance, route.cost()))
+            .map(|(index, _)| index)
+        {
+            None => break,
+            Some(index) => enumerated.push(candidates.swap_remove(index)),
+        }
+    }
+
+    Ok(ret.iter().map(Route::path).collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::rc::Rc;
+
+    use robotics_lib::world::tile::TileType;
+
+    use super::*;
+    use crate::test_utils::{conditions, tile, RainFrom, Rng};
+
+    /// loopless_distances pushes to ret the search distance of every loopless path from the last visited tile to the destination.
+    fn loopless_distances(
+        graph: &Graph,
+        visited: &mut Vec<(usize, usize)>,
+        destination: (usize, usize),
+        distance: usize,
+        ret: &mut Vec<usize>,
+    ) {
+        let node = visited[visited.len() - 1];
+
+        if node == destination {
+            ret.push(distance);
+            return;
+        }
+
+        let mut edges = Vec::new();
+        graph.edges(node, visited.len() - 1, &mut edges);
+
+        for edge in edges {
+            if !visited.contains(&edge.node) {
+                visited.push(edge.node);
+                loopless_distances(graph, visited, destination, distance + edge.distance, ret);
+                visited.pop();
+            }
+        }
+    }
+
+    #[test]
+    fn alternatives_are_the_shortest_loopless_paths_with_a_forecast() {
+        const TILE_TYPES: [TileType; 4] = [
+            TileType::Grass,
+            TileType::Sand,
+            TileType::Hill,
+            TileType::Wall,
+        ];
+
+        let environmental_conditions = conditions();
+        let (source, destination) = ((0, 0), (3, 3));
+        let mut rng = Rng::new(1);
+
+        for _ in 0..300 {
+            let mut map: Vec<Vec<Option<Tile>>> = (0..4)
+                .map(|_| {
+                    (0..4)
+                        .map(|_| Some(tile(TILE_TYPES[rng.next(TILE_TYPES.len())])))
+                        .collect()
+                })
+                .collect();
+            map[source.0][source.1] = Some(tile(TileType::Grass));
+            map[destination.0][destination.1] = Some(tile(TileType::Grass));
+
+            let rain_from = rng.next(8);
+            let options = SearchOptions {
+                forecast: Some(Rc::new(RainFrom(rain_from))),
+                ..SearchOptions::default()
+            };
+            let graph = Graph::new(&map, &environmental_conditions, &options);
+
+            let mut expected = Vec::new();
+            loopless_distances(&graph, &mut vec![source], destination, 0, &mut expected);
+            expected.sort();
+            expected.truncate(6);
+
+            let paths = match alternatives(
+                &environmental_conditions,
+                &map,
+                source,
+                HashSet::from([destination]),
+                6,
+                None,
+                &options,
+            ) {
+                Ok(paths) => paths,
+                Err(TomTomError::Unreachable) if expected.is_empty() => continue,
+                Err(e) => panic!("{e:?}"),
+            };
+
+            let distances: Vec<usize> = paths
+                .iter()
+                .map(|path| Route::new(&graph, path, 0).unwrap().distance)
+                .collect();
+
+            assert_eq!(distances, expected, "rain from step {rain_from}");
+
+            for path in paths.iter() {
+                assert_eq!(
+                    path.breakdown.iter().map(|step| step.cost).sum::<usize>(),
+                    path.cost
+                );
+            }
+        }
+    }
+}
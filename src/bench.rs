@@ -0,0 +1,148 @@
+//! Benchmark of the search against the implementation preceding the search tree, on a 500×500 map.
+//! Run it with: cargo test --release bench -- --ignored --nocapture
+
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashSet};
+use std::time::{Duration, Instant};
+
+use robotics_lib::interface::Direction;
+use robotics_lib::world::environmental_conditions::EnvironmentalConditions;
+use robotics_lib::world::tile::{Tile, TileType};
+
+use crate::dijkstra::dijkstra;
+use crate::path::{Action, Path};
+use crate::search::{SearchAlgorithm, SearchOptions};
+use crate::test_utils::{conditions, random_map, tile, Rng};
+use crate::utils::{calculate_go_cost, calculate_teleport_cost, get_next_coords};
+
+/// baseline is the search preceding the search tree: every tile stores a whole path, whose actions are cloned at every relaxation.
+fn baseline(
+    environmental_conditions: &EnvironmentalConditions,
+    map: &[Vec<Option<Tile>>],
+    source: (usize, usize),
+    targets: HashSet<(usize, usize)>,
+) -> Option<Path> {
+    let size = map.len();
+
+    let mut paths: Vec<Vec<Path>> = (0..size)
+        .map(|row| {
+            (0..size)
+                .map(|col| Path::new(source, (row, col), usize::MAX))
+                .collect()
+        })
+        .collect();
+    let mut teleports = Vec::new();
+
+    for (row, tiles) in map.iter().enumerate() {
+        for (col, tile) in tiles.iter().enumerate() {
+            if let Some(tile) = tile {
+                if tile.tile_type == TileType::Teleport(true) {
+                    teleports.push((row, col));
+                }
+            }
+        }
+    }
+
+    paths[source.0][source.1].cost = 0;
+
+    let mut heap = BinaryHeap::new();
+    heap.push(Reverse((0, source)));
+
+    while let Some(Reverse((distance, (row, col)))) = heap.pop() {
+        for direction in [
+            Direction::Right,
+            Direction::Down,
+            Direction::Left,
+            Direction::Up,
+        ] {
+            let next = match get_next_coords(size, (row, col), &direction) {
+                Some(next) if map[next.0][next.1].is_some() => next,
+                _ => continue,
+            };
+
+            if let Ok(cost) =
+                calculate_go_cost(environmental_conditions, map, (row, col), direction.clone())
+            {
+                if distance + cost < paths[next.0][next.1].cost {
+                    paths[next.0][next.1].cost = distance + cost;
+                    paths[next.0][next.1].actions = paths[row][col].actions.clone();
+                    paths[next.0][next.1]
+                        .actions
+                        .push_back(Action::Go(direction));
+                    heap.push(Reverse((distance + cost, next)));
+                }
+            }
+        }
+
+        if map[row][col].as_ref().map(|tile| tile.tile_type) == Some(TileType::Teleport(true)) {
+            for teleport in teleports.iter() {
+                if let Ok(cost) = calculate_teleport_cost(map, (row, col), *teleport) {
+                    if distance + cost < paths[teleport.0][teleport.1].cost {
+                        paths[teleport.0][teleport.1].cost = distance + cost;
+                        paths[teleport.0][teleport.1].actions = paths[row][col].actions.clone();
+                        paths[teleport.0][teleport.1]
+                            .actions
+                            .push_back(Action::Teleport(*teleport));
+                        heap.push(Reverse((distance + cost, *teleport)));
+                    }
+                }
+            }
+        }
+    }
+
+    targets
+        .into_iter()
+        .map(|(row, col)| paths[row][col].clone())
+        .filter(|path| path.cost != usize::MAX)
+        .min_by_key(|path| path.cost)
+}
+
+fn time<R>(f: impl FnOnce() -> R) -> (R, Duration) {
+    let start = Instant::now();
+    let ret = f();
+    (ret, start.elapsed())
+}
+
+#[test]
+#[ignore]
+fn bench_search_500x500() {
+    let environmental_conditions = conditions();
+    let mut rng = Rng::new(500);
+    let mut map = random_map(&mut rng, 500, 20);
+
+    let (source, destination) = ((0, 0), (499, 499));
+    map[source.0][source.1] = Some(tile(TileType::Grass));
+    map[destination.0][destination.1] = Some(tile(TileType::Grass));
+    let targets = HashSet::from([destination]);
+
+    let (expected, baseline_time) = time(|| {
+        baseline(&environmental_conditions, &map, source, targets.clone())
+            .expect("the destination is reachable")
+    });
+
+    for algorithm in [SearchAlgorithm::Dijkstra, SearchAlgorithm::AStar] {
+        let options = SearchOptions {
+            algorithm,
+            ..SearchOptions::default()
+        };
+
+        let (path, search_time) = time(|| {
+            dijkstra(
+                &environmental_conditions,
+                &map,
+                source,
+                targets.clone(),
+                &options,
+            )
+            .expect("the destination is reachable")
+        });
+
+        println!(
+            "{algorithm:?}: {search_time:?}, baseline: {baseline_time:?} ({:.1}x)",
+            baseline_time.as_secs_f64() / search_time.as_secs_f64()
+        );
+
+        assert_eq!(path.cost, expected.cost);
+        assert_eq!(path.breakdown.len(), path.actions.len());
+    }
+}
@@ -0,0 +1,57 @@
+use std::collections::{HashMap, HashSet};
+
+use robotics_lib::world::tile::Tile;
+
+use crate::plain::{PlainContent, PlainTileType};
+
+/// Constraints describes the tiles a path must avoid and the tiles a path should avoid when possible.
+/// Hard exclusions make a tile unreachable, soft penalties are added to the search distance of the actions
+/// reaching a tile, without changing the energy cost of the path.
+///
+/// # Fields:
+/// - excluded_coordinates: HashSet<(usize, usize)> => tiles of coordinates (row, col) the path can't reach.
+/// - excluded_tile_types: HashSet<PlainTileType> => tile types the path can't reach.
+/// - excluded_contents: HashSet<PlainContent> => contents the path can't reach.
+/// - coordinates_penalties: HashMap<(usize, usize), usize> => penalty of reaching the tiles of coordinates (row, col).
+/// - tile_types_penalties: HashMap<PlainTileType, usize> => penalty of reaching the tiles of the tile types.
+/// - contents_penalties: HashMap<PlainContent, usize> => penalty of reaching the tiles having the contents.
+#[derive(Debug, Default, Clone)]
+pub struct Constraints {
+    pub excluded_coordinates: HashSet<(usize, usize)>,
+    pub excluded_tile_types: HashSet<PlainTileType>,
+    pub excluded_contents: HashSet<PlainContent>,
+    pub coordinates_penalties: HashMap<(usize, usize), usize>,
+    pub tile_types_penalties: HashMap<PlainTileType, usize>,
+    pub contents_penalties: HashMap<PlainContent, usize>,
+}
+
+impl Constraints {
+    /// is_excluded returns true if the tile of coordinates (row, col) can't be reached.
+    pub fn is_excluded(&self, coordinates: (usize, usize), tile: &Tile) -> bool {
+        self.excluded_coordinates.contains(&coordinates)
+            || self
+                .excluded_tile_types
+                .contains(&PlainTileType::from(&tile.tile_type))
+            || self
+                .excluded_contents
+                .contains(&PlainContent::from(&tile.content))
+    }
+
+    /// penalty returns the sum of the penalties of reaching the tile of coordinates (row, col).
+    pub fn penalty(&self, coordinates: (usize, usize), tile: &Tile) -> usize {
+        self.coordinates_penalties
+            .get(&coordinates)
+            .copied()
+            .unwrap_or(0)
+            + self
+                .tile_types_penalties
+                .get(&PlainTileType::from(&tile.tile_type))
+                .copied()
+                .unwrap_or(0)
+            + self
+                .contents_penalties
+                .get(&PlainContent::from(&tile.content))
+                .copied()
+                .unwrap_or(0)
+    }
+}
@@ -0,0 +1,250 @@
+use robotics_lib::interface::Direction;
+use robotics_lib::world::environmental_conditions::EnvironmentalConditions;
+use robotics_lib::world::tile::Tile;
+
+use crate::error::TomTomError;
+use crate::utils::{calculate_go_cost, calculate_min_go_cost, calculate_teleport_cost};
+
+/// CostModel describes the energy cost of the actions considered while searching a path.
+pub trait CostModel {
+    /// go_cost returns the cost of moving from the source tile to the adjacent tile in the given direction.
+    ///
+    /// # Arguments
+    /// - map: &[Vec<Option<Tile>>] => map known by the robot.
+    /// - environmental_conditions: &EnvironmentalConditions => environmental conditions at the time of the action.
+    /// - source: (usize, usize) => source tile of coordinates (row, col).
+    /// - direction: Direction => direction of the action, the caller guarantees the destination tile is within the map bounds.
+    ///
+    /// # Return
+    /// - Result<usize, TomTomError> => Ok(cost) returns the cost, Err(e) means the action is not allowed for the reason described by TomTomError e.
+    fn go_cost(
+        &self,
+        map: &[Vec<Option<Tile>>],
+        environmental_conditions: &EnvironmentalConditions,
+        source: (usize, usize),
+        direction: Direction,
+    ) -> Result<usize, TomTomError>;
+
+    /// teleport_cost returns the cost of teleporting from the source tile to the destination tile.
+    ///
+    /// # Arguments
+    /// - map: &[Vec<Option<Tile>>] => map known by the robot.
+    /// - source: (usize, usize) => source tile of coordinates (row, col).
+    /// - destination: (usize, usize) => destination tile of coordinates (row, col).
+    ///
+    /// # Return
+    /// - Result<usize, TomTomError> => Ok(cost) returns the cost, Err(e) means the action is not allowed for the reason described by TomTomError e.
+    fn teleport_cost(
+        &self,
+        map: &[Vec<Option<Tile>>],
+        source: (usize, usize),
+        destination: (usize, usize),
+    ) -> Result<usize, TomTomError>;
+
+    /// min_go_cost returns a lower bound of go_cost, used by SearchAlgorithm::AStar to estimate the remaining cost.
+    /// The default implementation returns 0, which is always a valid lower bound.
+    fn min_go_cost(&self, _environmental_conditions: &EnvironmentalConditions) -> usize {
+        0
+    }
+}
+
+/// DefaultCostModel is the cost model of the robotics_lib interfaces: go costs the destination tile's cost adjusted by the environmental conditions,
+/// plus the square of the elevation gain, and teleport costs 30 between active teleports.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct DefaultCostModel;
+
+impl CostModel for DefaultCostModel {
+    fn go_cost(
+        &self,
+        map: &[Vec<Option<Tile>>],
+        environmental_conditions: &EnvironmentalConditions,
+        source: (usize, usize),
+        direction: Direction,
+    ) -> Result<usize, TomTomError> {
+        calculate_go_cost(environmental_conditions, map, source, direction)
+    }
+
+    fn teleport_cost(
+        &self,
+        map: &[Vec<Option<Tile>>],
+        source: (usize, usize),
+        destination: (usize, usize),
+    ) -> Result<usize, TomTomError> {
+        calculate_teleport_cost(map, source, destination)
+    }
+
+    fn min_go_cost(&self, environmental_conditions: &EnvironmentalConditions) -> usize {
+        calculate_min_go_cost(environmental_conditions)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+    use std::rc::Rc;
+
+    use robotics_lib::world::tile::TileType;
+
+    use super::*;
+    use crate::dijkstra::{dijkstra, Graph};
+    use crate::path::Action;
+    use crate::search::{SearchAlgorithm, SearchOptions};
+    use crate::test_utils::{conditions, map, random_map, Rng};
+    use crate::utils::calculate_action_cost;
+
+    /// SandCostModel makes entering sand prohibitive, otherwise it costs like DefaultCostModel.
+    struct SandCostModel;
+
+    impl CostModel for SandCostModel {
+        fn go_cost(
+            &self,
+            map: &[Vec<Option<Tile>>],
+            environmental_conditions: &EnvironmentalConditions,
+            source: (usize, usize),
+            direction: Direction,
+        ) -> Result<usize, TomTomError> {
+            let cost = DefaultCostModel.go_cost(
+                map,
+                environmental_conditions,
+                source,
+                direction.clone(),
+            )?;
+
+            let (row, col) = source;
+            let destination = match direction {
+                Direction::Up => (row - 1, col),
+                Direction::Down => (row + 1, col),
+                Direction::Left => (row, col - 1),
+                Direction::Right => (row, col + 1),
+            };
+
+            match map[destination.0][destination.1].as_ref() {
+                Some(tile) if tile.tile_type == TileType::Sand => Ok(100),
+                _ => Ok(cost),
+            }
+        }
+
+        fn teleport_cost(
+            &self,
+            map: &[Vec<Option<Tile>>],
+            source: (usize, usize),
+            destination: (usize, usize),
+        ) -> Result<usize, TomTomError> {
+            DefaultCostModel.teleport_cost(map, source, destination)
+        }
+    }
+
+    /// SurchargeCostModel adds 2 to every go action of DefaultCostModel, so that its lower bound is not 0.
+    struct SurchargeCostModel;
+
+    impl CostModel for SurchargeCostModel {
+        fn go_cost(
+            &self,
+            map: &[Vec<Option<Tile>>],
+            environmental_conditions: &EnvironmentalConditions,
+            source: (usize, usize),
+            direction: Direction,
+        ) -> Result<usize, TomTomError> {
+            DefaultCostModel
+                .go_cost(map, environmental_conditions, source, direction)
+                .map(|cost| cost + 2)
+        }
+
+        fn teleport_cost(
+            &self,
+            map: &[Vec<Option<Tile>>],
+            source: (usize, usize),
+            destination: (usize, usize),
+        ) -> Result<usize, TomTomError> {
+            DefaultCostModel.teleport_cost(map, source, destination)
+        }
+
+        fn min_go_cost(&self, environmental_conditions: &EnvironmentalConditions) -> usize {
+            DefaultCostModel.min_go_cost(environmental_conditions) + 2
+        }
+    }
+
+    fn options(cost_model: Rc<dyn CostModel>, algorithm: SearchAlgorithm) -> SearchOptions {
+        SearchOptions {
+            algorithm,
+            cost_model,
+            ..SearchOptions::default()
+        }
+    }
+
+    #[test]
+    fn search_and_action_costs_use_the_cost_model() {
+        let environmental_conditions = conditions();
+        let map = map(&[". : .", ". # .", ". . ."]);
+        let targets = HashSet::from([(0, 2)]);
+
+        let default_path = dijkstra(
+            &environmental_conditions,
+            &map,
+            (0, 0),
+            targets.clone(),
+            &SearchOptions::default(),
+        )
+        .unwrap();
+        assert_eq!(default_path.actions.len(), 2);
+
+        let sand_options = options(Rc::new(SandCostModel), SearchAlgorithm::Dijkstra);
+        let path = dijkstra(
+            &environmental_conditions,
+            &map,
+            (0, 0),
+            targets,
+            &sand_options,
+        )
+        .unwrap();
+
+        assert_eq!(path.actions.len(), 6);
+        assert!(!path.contains((0, 1)));
+        assert!(path.cost > default_path.cost);
+
+        assert!(matches!(
+            calculate_action_cost(
+                &SandCostModel,
+                &environmental_conditions,
+                &map,
+                (0, 0),
+                &Action::Go(Direction::Right),
+            ),
+            Ok(100)
+        ));
+        assert_eq!(
+            path.breakdown.iter().map(|step| step.cost).sum::<usize>(),
+            path.cost
+        );
+    }
+
+    #[test]
+    fn a_star_returns_the_same_cost_as_dijkstra_with_a_positive_lower_bound() {
+        let environmental_conditions = conditions();
+        let cost_model: Rc<dyn CostModel> = Rc::new(SurchargeCostModel);
+        let mut rng = Rng::new(7);
+
+        for _ in 0..200 {
+            let teleports = rng.next(3);
+            let map = random_map(&mut rng, 10, teleports);
+            let source = (rng.next(10), rng.next(10));
+            let targets = HashSet::from([(rng.next(10), rng.next(10))]);
+
+            let a_star = options(cost_model.clone(), SearchAlgorithm::AStar);
+            assert!(Graph::new(&map, &environmental_conditions, &a_star).min_go_cost() > 0);
+
+            let expected = dijkstra(
+                &environmental_conditions,
+                &map,
+                source,
+                targets.clone(),
+                &options(cost_model.clone(), SearchAlgorithm::Dijkstra),
+            )
+            .map(|path| path.cost);
+            let actual = dijkstra(&environmental_conditions, &map, source, targets, &a_star)
+                .map(|path| path.cost);
+
+            assert_eq!(expected.ok(), actual.ok());
+        }
+    }
+}
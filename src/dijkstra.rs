@@ -1,20 +1,34 @@
-use crate::path::{Action, Path};
-use crate::utils::{calculate_go_cost, calculate_teleport_cost};
+use crate::error::TomTomError;
+use crate::path::{Action, Path, StepCost};
+use crate::search::{SearchAlgorithm, SearchOptions};
+use crate::utils::{
+    calculate_go_step_cost, get_action_destination, get_next_coords, manhattan_distance,
+};
 use robotics_lib::interface::Direction;
-use robotics_lib::runner::Runnable;
-use robotics_lib::world::{tile::Tile, tile::TileType, World};
-use std::cmp::Ordering;
-use std::collections::{BinaryHeap, HashSet};
+use robotics_lib::world::environmental_conditions::EnvironmentalConditions;
+use robotics_lib::world::{tile::Tile, tile::TileType};
+use std::cell::RefCell;
+use std::cmp::{Ordering, Reverse};
+use std::collections::{BinaryHeap, HashSet, VecDeque};
 
 #[derive(Eq)]
 struct State {
-    node: (usize, usize),
+    index: usize,
     distance: usize,
+    estimate: usize,
+}
+
+impl State {
+    /// key orders the states in the heap: smallest estimate first, then greatest distance (the state nearer to a target), then node index,
+    /// so that equal states are exactly the states with equal keys.
+    fn key(&self) -> (Reverse<usize>, usize, usize) {
+        (Reverse(self.estimate), self.distance, self.index)
+    }
 }
 
 impl Ord for State {
     fn cmp(&self, other: &Self) -> Ordering {
-        other.distance.cmp(&self.distance)
+        self.key().cmp(&other.key())
     }
 }
 
@@ -26,157 +40,1031 @@ impl PartialOrd for State {
 
 impl PartialEq for State {
     fn eq(&self, other: &Self) -> bool {
-        self.node == other.node && self.distance == other.distance
+        self.key() == other.key()
     }
 }
 
-pub(crate) fn dijkstra(
-    robot: &impl Runnable,
-    world: &World,
-    map: &Vec<Vec<Option<Tile>>>,
+/// Heuristic estimates the remaining energy cost from a tile to the nearest target.
+/// The estimate is the Manhattan distance scaled by the cheapest walkable tile cost, either walking straight to a target
+/// or walking to a teleport and then from any teleport to a target (teleport cost is bounded by 0), so it never overestimates.
+pub(crate) struct Heuristic {
+    targets: Vec<(usize, usize)>,
+    teleports: Vec<(usize, usize)>,
+    teleport_exit: Option<usize>,
+    min_go_cost: usize,
+}
+
+impl Heuristic {
+    pub(crate) fn new(
+        targets: &HashSet<(usize, usize)>,
+        teleports: &[(usize, usize)],
+        min_go_cost: usize,
+    ) -> Heuristic {
+        let targets: Vec<(usize, usize)> = targets.iter().copied().collect();
+
+        let teleport_exit = teleports
+            .iter()
+            .flat_map(|teleport| {
+                targets
+                    .iter()
+                    .map(move |target| manhattan_distance(*teleport, *target))
+            })
+            .min();
+
+        Heuristic {
+            targets,
+            teleports: teleports.to_vec(),
+            teleport_exit,
+            min_go_cost,
+        }
+    }
+
+    pub(crate) fn estimate(&self, node: (usize, usize)) -> usize {
+        let mut steps = self
+            .targets
+            .iter()
+            .map(|target| manhattan_distance(node, *target))
+            .min()
+            .unwrap_or(0);
+
+        if let Some(teleport_exit) = self.teleport_exit {
+            if let Some(teleport_entry) = self
+                .teleports
+                .iter()
+                .map(|teleport| manhattan_distance(node, *teleport))
+                .min()
+            {
+                steps = steps.min(teleport_entry + teleport_exit);
+            }
+        }
+
+        steps * self.min_go_cost
+    }
+}
+
+/// Parent is the index of the predecessor node in the search tree, together with the action reaching the node and whether the action is unknown.
+type Parent = (usize, Action, bool);
+
+pub(crate) type BlockedEdges = HashSet<((usize, usize), (usize, usize))>;
+
+/// Node is the state of a tile, reached by a number of actions, in the search tree.
+#[derive(Clone)]
+struct Node {
+    distance: usize,
+    cost: usize,
+    steps: usize,
+    parent: Option<Parent>,
+}
+
+/// SearchTree stores, for every tile of the map and every layer, the smallest search distance found from the source tile, its energy cost,
+/// its number of actions and the predecessor node together with the action that reaches it, so that a path and its cost breakdown
+/// are rebuilt only when requested. The distance is the energy cost plus the penalties of the constraints.
+/// The layer of a node is its number of actions, up to the last layer that gathers the nodes reached by more actions:
+/// without a forecast there is a single layer, with a forecast there is a layer for every step up to its horizon,
+/// so that a tile reached by more actions at a smaller distance doesn't prune a tile reached by fewer actions.
+pub(crate) struct SearchTree {
     source: (usize, usize),
-    targets: HashSet<(usize, usize)>,
-) -> Result<Path, String> {
-    if targets.is_empty() {
-        return Err(String::from("Path not found!"));
+    size: usize,
+    layers: usize,
+    nodes: Vec<Node>,
+}
+
+impl SearchTree {
+    /// new returns the tree of the source tile, whose first action is performed as the action of the given step.
+    fn new(size: usize, layers: usize, source: (usize, usize), first_step: usize) -> SearchTree {
+        let mut tree = SearchTree {
+            source,
+            size,
+            layers,
+            nodes: vec![
+                Node {
+                    distance: usize::MAX,
+                    cost: usize::MAX,
+                    steps: 0,
+                    parent: None,
+                };
+                size * size * layers
+            ],
+        };
+
+        let index = tree.index(source, first_step);
+        tree.nodes[index].distance = 0;
+        tree.nodes[index].cost = 0;
+        tree.nodes[index].steps = first_step;
+
+        tree
     }
 
-    let (source_row, source_col) = (source.0, source.1);
-    let size = map.len();
+    /// index returns the index of the node of the tile reached by the given number of actions.
+    fn index(&self, node: (usize, usize), steps: usize) -> usize {
+        (steps.min(self.layers - 1) * self.size + node.0) * self.size + node.1
+    }
 
-    if source_row >= size || source_col >= size {
-        return Err(String::from("Source out of bounds!"));
+    /// tile returns the tile of the node at the index.
+    fn tile(&self, index: usize) -> (usize, usize) {
+        let index = index % (self.size * self.size);
+        (index / self.size, index % self.size)
+    }
+
+    /// best returns the index of the node of the tile with the smallest distance among the layers, None if the tile isn't reached.
+    fn best(&self, node: (usize, usize)) -> Option<usize> {
+        if node.0 >= self.size || node.1 >= self.size {
+            return None;
+        }
+
+        (0..self.layers)
+            .map(|layer| (layer * self.size + node.0) * self.size + node.1)
+            .filter(|index| self.nodes[*index].distance != usize::MAX)
+            .min_by_key(|index| self.nodes[*index].distance)
+    }
+
+    /// relax records the edge from the parent node if it reaches its tile at a smaller distance, returning the index of the reached node.
+    fn relax(&mut self, parent: usize, edge: &Edge, distance: usize) -> Option<usize> {
+        let (parent_cost, parent_steps) = (self.nodes[parent].cost, self.nodes[parent].steps);
+        let index = self.index(edge.node, parent_steps + 1);
+        let node = &mut self.nodes[index];
+
+        if distance < node.distance {
+            node.distance = distance;
+            node.cost = parent_cost + edge.cost;
+            node.steps = parent_steps + 1;
+            node.parent = Some((parent, edge.action.clone(), edge.unknown));
+            Some(index)
+        } else {
+            None
+        }
+    }
+
+    pub(crate) fn distance(&self, node: (usize, usize)) -> Option<usize> {
+        self.best(node).map(|index| self.nodes[index].distance)
+    }
+
+    pub(crate) fn cost(&self, node: (usize, usize)) -> Option<usize> {
+        self.best(node).map(|index| self.nodes[index].cost)
     }
 
-    let mut paths = Vec::new();
-    let mut teleports = Vec::new();
+    /// path_to rebuilds the path reaching the destination tile, costing the breakdown of its actions with the graph that was searched.
+    pub(crate) fn path_to(&self, graph: &Graph, destination: (usize, usize)) -> Option<Path> {
+        let mut index = self.best(destination)?;
+        let cost = self.nodes[index].cost;
 
-    for row in 0..size {
-        paths.push(Vec::new());
+        let mut actions = VecDeque::new();
+        let mut breakdown = VecDeque::new();
+        let mut unknown_steps = 0;
 
-        for col in 0..size {
-            paths[row].push(Path::new(source, (row, col), usize::MAX));
+        while let Node {
+            distance,
+            cost,
+            parent: Some((parent, action, unknown)),
+            ..
+        } = &self.nodes[index]
+        {
+            let parent_node = &self.nodes[*parent];
+            let edge = Edge {
+                node: self.tile(index),
+                action: action.clone(),
+                distance: distance - parent_node.distance,
+                cost: cost - parent_node.cost,
+                unknown: *unknown,
+            };
 
-            if let Some(tile) = map[row][col].as_ref() {
-                if tile.tile_type == TileType::Teleport(true) {
-                    teleports.push((row, col));
+            breakdown.push_front(graph.step_cost(self.tile(*parent), &edge, parent_node.steps));
+            actions.push_front(edge.action);
+            unknown_steps += usize::from(*unknown);
+            index = *parent;
+        }
+
+        let mut path = Path::new(self.source, destination, cost);
+        path.actions = actions;
+        path.breakdown = breakdown.into();
+        path.unknown_steps = unknown_steps;
+
+        Some(path)
+    }
+
+    pub(crate) fn nearest_path(
+        &self,
+        graph: &Graph,
+        targets: &HashSet<(usize, usize)>,
+    ) -> Option<Path> {
+        targets
+            .iter()
+            .filter_map(|target| self.distance(*target).map(|distance| (distance, *target)))
+            .min()
+            .and_then(|(_, target)| self.path_to(graph, target))
+    }
+}
+
+/// Edge describes an action available from a tile while searching a path.
+#[derive(Clone)]
+pub(crate) struct Edge {
+    pub(crate) node: (usize, usize),
+    pub(crate) action: Action,
+    pub(crate) distance: usize,
+    pub(crate) cost: usize,
+    pub(crate) unknown: bool,
+}
+
+/// Graph describes the actions available from every tile of the map known by the robot, according to the search options:
+/// the distance of an edge is its energy cost plus the penalty of the reached tile.
+/// If the options have a forecast, the go actions are costed with the conditions expected at their step, which are computed once,
+/// the actions after the horizon of the forecast being costed with the conditions of the horizon.
+pub(crate) struct Graph<'a> {
+    map: &'a [Vec<Option<Tile>>],
+    environmental_conditions: &'a EnvironmentalConditions,
+    options: &'a SearchOptions,
+    teleports: Vec<(usize, usize)>,
+    forecasts: RefCell<Vec<Option<EnvironmentalConditions>>>,
+}
+
+impl<'a> Graph<'a> {
+    pub(crate) fn new(
+        map: &'a [Vec<Option<Tile>>],
+        environmental_conditions: &'a EnvironmentalConditions,
+        options: &'a SearchOptions,
+    ) -> Graph<'a> {
+        let mut teleports = Vec::new();
+
+        for (row, tiles) in map.iter().enumerate() {
+            for (col, tile) in tiles.iter().enumerate() {
+                if let Some(tile) = tile {
+                    if tile.tile_type == TileType::Teleport(true) {
+                        teleports.push((row, col));
+                    }
                 }
             }
         }
+
+        Graph {
+            map,
+            environmental_conditions,
+            options,
+            teleports,
+            forecasts: RefCell::new(Vec::new()),
+        }
+    }
+
+    pub(crate) fn size(&self) -> usize {
+        self.map.len()
+    }
+
+    pub(crate) fn teleports(&self) -> &[(usize, usize)] {
+        &self.teleports
+    }
+
+    /// layers returns the number of layers of the search tree: one for every step up to the horizon of the forecast.
+    fn layers(&self) -> usize {
+        match &self.options.forecast {
+            None => 1,
+            Some(forecast) => forecast.horizon() + 1,
+        }
+    }
+
+    /// min_go_cost returns a lower bound of the cost of a go action, at any step of the forecast if any.
+    pub(crate) fn min_go_cost(&self) -> usize {
+        let cost_model = self.options.cost_model.as_ref();
+
+        let min_go_cost = match &self.options.forecast {
+            None => cost_model.min_go_cost(self.environmental_conditions),
+            Some(forecast) => (0..=forecast.horizon())
+                .map(|step| {
+                    self.with_conditions(step, |environmental_conditions| {
+                        cost_model.min_go_cost(environmental_conditions)
+                    })
+                })
+                .min()
+                .unwrap_or(0),
+        };
+
+        match self.options.unknown_cost {
+            None => min_go_cost,
+            Some(unknown_cost) => min_go_cost.min(unknown_cost),
+        }
+    }
+
+    fn with_conditions<R>(&self, step: usize, f: impl FnOnce(&EnvironmentalConditions) -> R) -> R {
+        let forecast = match &self.options.forecast {
+            None => return f(self.environmental_conditions),
+            Some(forecast) => forecast,
+        };
+
+        let step = step.min(forecast.horizon());
+        let mut forecasts = self.forecasts.borrow_mut();
+
+        while forecasts.len() <= step {
+            let next = forecasts.len();
+            forecasts.push(forecast.conditions(next));
+        }
+
+        match &forecasts[step] {
+            None => f(self.environmental_conditions),
+            Some(environmental_conditions) => f(environmental_conditions),
+        }
+    }
+
+    fn is_teleport(&self, node: (usize, usize)) -> bool {
+        match &self.map[node.0][node.1] {
+            None => false,
+            Some(tile) => tile.tile_type == TileType::Teleport(true),
+        }
+    }
+
+    fn penalty(&self, node: (usize, usize)) -> Option<usize> {
+        let constraints = &self.options.constraints;
+
+        match &self.map[node.0][node.1] {
+            None => match self.options.unknown_cost {
+                Some(_) if !constraints.excluded_coordinates.contains(&node) => Some(
+                    constraints
+                        .coordinates_penalties
+                        .get(&node)
+                        .copied()
+                        .unwrap_or(0),
+                ),
+                _ => None,
+            },
+            Some(tile) if constraints.is_excluded(node, tile) => None,
+            Some(tile) => Some(constraints.penalty(node, tile)),
+        }
+    }
+
+    /// edges replaces the content of ret with the actions available from the tile as the action of the given step,
+    /// in the order Right, Down, Left, Up, then the teleports. The buffer is reused to avoid an allocation for every expanded tile.
+    pub(crate) fn edges(&self, node: (usize, usize), step: usize, ret: &mut Vec<Edge>) {
+        ret.clear();
+
+        let cost_model = self.options.cost_model.as_ref();
+
+        for direction in [
+            Direction::Right,
+            Direction::Down,
+            Direction::Left,
+            Direction::Up,
+        ] {
+            let (next, penalty) = match get_next_coords(self.size(), node, &direction) {
+                Some(next) => match self.penalty(next) {
+                    Some(penalty) => (next, penalty),
+                    None => continue,
+                },
+                None => continue,
+            };
+
+            let unknown = self.map[node.0][node.1].is_none() || self.map[next.0][next.1].is_none();
+
+            let cost = match self.options.unknown_cost {
+                Some(unknown_cost) if unknown => match &self.map[next.0][next.1] {
+                    Some(tile) if !tile.tile_type.properties().walk() => {
+                        Err(TomTomError::NotWalkable(next))
+                    }
+                    _ => Ok(unknown_cost),
+                },
+                _ => self.with_conditions(step, |environmental_conditions| {
+                    cost_model.go_cost(self.map, environmental_conditions, node, direction.clone())
+                }),
+            };
+
+            if let Ok(cost) = cost {
+                ret.push(Edge {
+                    node: next,
+                    action: Action::Go(direction),
+                    distance: cost + penalty,
+                    cost,
+                    unknown,
+                });
+            }
+        }
+
+        if self.is_teleport(node) {
+            for teleport in self.teleports.iter().filter(|teleport| **teleport != node) {
+                let penalty = match self.penalty(*teleport) {
+                    Some(penalty) => penalty,
+                    None => continue,
+                };
+
+                if let Ok(cost) = cost_model.teleport_cost(self.map, node, *teleport) {
+                    ret.push(Edge {
+                        node: *teleport,
+                        action: Action::Teleport(*teleport),
+                        distance: cost + penalty,
+                        cost,
+                        unknown: false,
+                    });
+                }
+            }
+        }
+    }
+
+    /// step_cost returns the cost breakdown of the edge from the tile, as the action of the given step.
+    pub(crate) fn step_cost(&self, node: (usize, usize), edge: &Edge, step: usize) -> StepCost {
+        let step_cost = match &edge.action {
+            Action::Go(direction) if !edge.unknown => {
+                self.with_conditions(step, |environmental_conditions| {
+                    calculate_go_step_cost(
+                        environmental_conditions,
+                        self.map,
+                        node,
+                        direction.clone(),
+                    )
+                    .ok()
+                })
+            }
+            Action::Teleport(_) => Some(StepCost {
+                teleport_cost: edge.cost,
+                ..StepCost::default()
+            }),
+            _ => None,
+        };
+
+        StepCost {
+            coordinates: edge.node,
+            cost: edge.cost,
+            unknown: edge.unknown,
+            ..step_cost.unwrap_or_default()
+        }
+    }
+
+    /// path returns the path performing the actions from the source tile, costed as the actions of the steps from first_step,
+    /// None if an action is not available from the tile it is performed on.
+    pub(crate) fn path<'b>(
+        &self,
+        source: (usize, usize),
+        actions: impl IntoIterator<Item = &'b Action>,
+        first_step: usize,
+    ) -> Option<Path> {
+        let mut path = Path::new(source, source, 0);
+        let mut edges = Vec::new();
+
+        for (step, action) in actions.into_iter().enumerate() {
+            let destination = get_action_destination(self.size(), path.destination, action)?;
+
+            self.edges(path.destination, first_step + step, &mut edges);
+
+            let edge = edges.drain(..).find(|edge| {
+                edge.node == destination
+                    && matches!(
+                        (&edge.action, action),
+                        (Action::Go(_), Action::Go(_)) | (Action::Teleport(_), Action::Teleport(_))
+                    )
+            })?;
+
+            path.cost += edge.cost;
+            path.unknown_steps += usize::from(edge.unknown);
+            path.breakdown
+                .push(self.step_cost(path.destination, &edge, first_step + step));
+            path.actions.push_back(edge.action);
+            path.destination = destination;
+        }
+
+        Some(path)
+    }
+
+    /// neighbours returns the tiles that may have an action reaching the tile.
+    pub(crate) fn neighbours(&self, node: (usize, usize)) -> Vec<(usize, usize)> {
+        let mut ret: Vec<(usize, usize)> = [
+            Direction::Right,
+            Direction::Down,
+            Direction::Left,
+            Direction::Up,
+        ]
+        .iter()
+        .filter_map(|direction| get_next_coords(self.size(), node, direction))
+        .collect();
+
+        if self.is_teleport(node) {
+            ret.extend(
+                self.teleports
+                    .iter()
+                    .filter(|teleport| **teleport != node)
+                    .copied(),
+            );
+        }
+
+        ret
+    }
+}
+
+/// search expands the graph from the source tile until a target is reached, or the whole graph if there are no targets.
+pub(crate) fn search(
+    graph: &Graph,
+    source: (usize, usize),
+    targets: &HashSet<(usize, usize)>,
+) -> Result<SearchTree, TomTomError> {
+    search_with_blocked_edges(graph, source, 0, targets, &HashSet::new())
+}
+
+/// search_with_blocked_edges searches like search, ignoring the actions from a tile to another tile of the blocked edges.
+/// The first action from the source tile is performed as the action of first_step, e.g. when the source is reached by other actions.
+pub(crate) fn search_with_blocked_edges(
+    graph: &Graph,
+    source: (usize, usize),
+    first_step: usize,
+    targets: &HashSet<(usize, usize)>,
+    blocked_edges: &BlockedEdges,
+) -> Result<SearchTree, TomTomError> {
+    let (source_row, source_col) = (source.0, source.1);
+    let size = graph.size();
+
+    if source_row >= size || source_col >= size {
+        return Err(TomTomError::SourceOutOfBounds);
     }
 
-    paths[source_row][source_col].cost = 0;
+    let mut tree = SearchTree::new(size, graph.layers(), source, first_step);
+
+    let heuristic = match graph.options.algorithm {
+        SearchAlgorithm::Dijkstra => None,
+        SearchAlgorithm::AStar => Some(Heuristic::new(
+            targets,
+            graph.teleports(),
+            graph.min_go_cost(),
+        )),
+    };
+    let estimate = |node: (usize, usize)| match &heuristic {
+        None => 0,
+        Some(heuristic) => heuristic.estimate(node),
+    };
 
     let mut heap = BinaryHeap::new();
     heap.push(State {
-        node: source,
+        index: tree.index(source, first_step),
         distance: 0,
+        estimate: estimate(source),
     });
 
-    while !heap.is_empty() {
-        let (row, col) = heap.peek().unwrap().node;
-        let distance = heap.pop().unwrap().distance;
-
-        if col + 1 < size && map[row][col + 1].is_some() {
-            if let Ok(cost) = calculate_go_cost(world, map, (row, col), Direction::Right) {
-                if distance + cost < paths[row][col + 1].cost {
-                    paths[row][col + 1].cost = distance + cost;
-                    paths[row][col + 1].actions = paths[row][col].actions.clone();
-                    paths[row][col + 1]
-                        .actions
-                        .push_back(Action::Go(Direction::Right));
-                    heap.push(State {
-                        node: (row, col + 1),
-                        distance: distance + cost,
-                    });
-                }
-            }
+    let mut edges = Vec::new();
+
+    while let Some(State {
+        index, distance, ..
+    }) = heap.pop()
+    {
+        if distance > tree.nodes[index].distance {
+            continue;
         }
 
-        if row + 1 < size && map[row + 1][col].is_some() {
-            if let Ok(cost) = calculate_go_cost(world, map, (row, col), Direction::Down) {
-                if distance + cost < paths[row + 1][col].cost {
-                    paths[row + 1][col].cost = distance + cost;
-                    paths[row + 1][col].actions = paths[row][col].actions.clone();
-                    paths[row + 1][col]
-                        .actions
-                        .push_back(Action::Go(Direction::Down));
-                    heap.push(State {
-                        node: (row + 1, col),
-                        distance: distance + cost,
-                    });
-                }
+        let node = tree.tile(index);
+
+        if targets.contains(&node) {
+            break;
+        }
+
+        graph.edges(node, tree.nodes[index].steps, &mut edges);
+
+        for edge in edges.iter() {
+            if blocked_edges.contains(&(node, edge.node)) {
+                continue;
+            }
+
+            let next_distance = distance + edge.distance;
+
+            if let Some(next) = tree.relax(index, edge, next_distance) {
+                heap.push(State {
+                    index: next,
+                    distance: next_distance,
+                    estimate: next_distance + estimate(edge.node),
+                });
             }
         }
+    }
 
-        if col > 0 && map[row][col - 1].is_some() {
-            if let Ok(cost) = calculate_go_cost(world, map, (row, col), Direction::Left) {
-                if distance + cost < paths[row][col - 1].cost {
-                    paths[row][col - 1].cost = distance + cost;
-                    paths[row][col - 1].actions = paths[row][col].actions.clone();
-                    paths[row][col - 1]
-                        .actions
-                        .push_back(Action::Go(Direction::Left));
-                    heap.push(State {
-                        node: (row, col - 1),
-                        distance: distance + cost,
-                    });
-                }
+    Ok(tree)
+}
+
+pub(crate) fn dijkstra(
+    environmental_conditions: &EnvironmentalConditions,
+    map: &[Vec<Option<Tile>>],
+    source: (usize, usize),
+    targets: HashSet<(usize, usize)>,
+    options: &SearchOptions,
+) -> Result<Path, TomTomError> {
+    if targets.is_empty() {
+        return Err(TomTomError::NoTargets);
+    }
+
+    let graph = Graph::new(map, environmental_conditions, options);
+    let tree = search(&graph, source, &targets)?;
+
+    tree.nearest_path(&graph, &targets)
+        .ok_or(TomTomError::Unreachable)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+    use std::rc::Rc;
+
+    use robotics_lib::world::environmental_conditions::WeatherType;
+
+    use super::*;
+    use crate::constraints::Constraints;
+    use crate::cost::{CostModel, DefaultCostModel};
+    use crate::plain::PlainTileType;
+    use crate::test_utils::{conditions, map, random_map, RainFrom, Rng};
+
+    fn options(algorithm: SearchAlgorithm) -> SearchOptions {
+        SearchOptions {
+            algorithm,
+            ..SearchOptions::default()
+        }
+    }
+
+    #[test]
+    fn states_are_equal_only_when_ordered_equal() {
+        let state = |index, distance, estimate| State {
+            index,
+            distance,
+            estimate,
+        };
+
+        let states = [
+            state(0, 3, 5),
+            state(1, 3, 5),
+            state(0, 4, 5),
+            state(0, 3, 4),
+        ];
+
+        for a in states.iter() {
+            for b in states.iter() {
+                assert_eq!(a == b, a.cmp(b) == Ordering::Equal);
             }
         }
 
-        if row > 0 && map[row - 1][col].is_some() {
-            if let Ok(cost) = calculate_go_cost(world, map, (row, col), Direction::Up) {
-                if distance + cost < paths[row - 1][col].cost {
-                    paths[row - 1][col].cost = distance + cost;
-                    paths[row - 1][col].actions = paths[row][col].actions.clone();
-                    paths[row - 1][col]
-                        .actions
-                        .push_back(Action::Go(Direction::Up));
-                    heap.push(State {
-                        node: (row - 1, col),
-                        distance: distance + cost,
-                    });
-                }
+        let mut heap = BinaryHeap::from(states);
+        assert_eq!(heap.pop().map(|state| state.estimate), Some(4));
+        assert_eq!(heap.pop().map(|state| state.distance), Some(4));
+    }
+
+    #[test]
+    fn a_star_returns_the_same_cost_as_dijkstra() {
+        let environmental_conditions = conditions();
+        let mut rng = Rng::new(1);
+
+        for _ in 0..200 {
+            let teleports = rng.next(4);
+            let map = random_map(&mut rng, 12, teleports);
+            let source = (rng.next(12), rng.next(12));
+            let targets =
+                HashSet::from([(rng.next(12), rng.next(12)), (rng.next(12), rng.next(12))]);
+
+            let expected = dijkstra(
+                &environmental_conditions,
+                &map,
+                source,
+                targets.clone(),
+                &options(SearchAlgorithm::Dijkstra),
+            )
+            .map(|path| path.cost);
+            let actual = dijkstra(
+                &environmental_conditions,
+                &map,
+                source,
+                targets,
+                &options(SearchAlgorithm::AStar),
+            )
+            .map(|path| path.cost);
+
+            assert_eq!(expected.ok(), actual.ok());
+        }
+    }
+
+    #[test]
+    fn path_breakdown_sums_to_its_cost() {
+        let environmental_conditions = conditions();
+        let mut rng = Rng::new(2);
+
+        for _ in 0..50 {
+            let map = random_map(&mut rng, 10, 3);
+            let source = (rng.next(10), rng.next(10));
+            let targets = HashSet::from([(rng.next(10), rng.next(10))]);
+
+            if let Ok(path) = dijkstra(
+                &environmental_conditions,
+                &map,
+                source,
+                targets,
+                &SearchOptions::default(),
+            ) {
+                assert_eq!(path.breakdown.len(), path.actions.len());
+                assert_eq!(
+                    path.breakdown.iter().map(|step| step.cost).sum::<usize>(),
+                    path.cost
+                );
+                assert!(path
+                    .coordinates()
+                    .skip(1)
+                    .zip(path.breakdown.iter())
+                    .all(|(coordinates, step)| coordinates == step.coordinates));
             }
         }
+    }
 
-        if let Some(tile) = map[row][col].as_ref() {
-            if tile.tile_type == TileType::Teleport(true) {
-                for (teleport_row, teleport_col) in teleports.iter() {
-                    if let Ok(cost) =
-                        calculate_teleport_cost(robot, map, (*teleport_row, *teleport_col))
-                    {
-                        if distance + cost < paths[*teleport_row][*teleport_col].cost {
-                            paths[*teleport_row][*teleport_col].cost = distance + cost;
-                            paths[*teleport_row][*teleport_col].actions =
-                                paths[row][col].actions.clone();
-                            paths[*teleport_row][*teleport_col]
-                                .actions
-                                .push_back(Action::Teleport((*teleport_row, *teleport_col)));
-                            heap.push(State {
-                                node: (*teleport_row, *teleport_col),
-                                distance: distance + cost,
-                            });
-                        }
-                    }
-                }
+    #[test]
+    fn walks_to_a_teleport_teleports_and_walks_to_the_target() {
+        let environmental_conditions = conditions();
+        let map = map(&[
+            ". . O # .",
+            ". . . # .",
+            ". . . # .",
+            ". . . # .",
+            ". . . # O",
+        ]);
+
+        for algorithm in [SearchAlgorithm::Dijkstra, SearchAlgorithm::AStar] {
+            let path = dijkstra(
+                &environmental_conditions,
+                &map,
+                (1, 0),
+                HashSet::from([(3, 4)]),
+                &options(algorithm),
+            )
+            .unwrap();
+
+            let teleport = path
+                .actions
+                .iter()
+                .position(|action| matches!(action, Action::Teleport(_)))
+                .unwrap();
+
+            assert!(teleport > 0 && teleport + 1 < path.actions.len());
+            assert!(matches!(path.actions[teleport], Action::Teleport((4, 4))));
+            assert!(path
+                .actions
+                .iter()
+                .enumerate()
+                .all(|(index, action)| index == teleport || matches!(action, Action::Go(_))));
+
+            let teleport_costs: Vec<usize> = path
+                .breakdown
+                .iter()
+                .map(|step| step.teleport_cost)
+                .filter(|teleport_cost| *teleport_cost > 0)
+                .collect();
+
+            assert_eq!(teleport_costs, vec![30]);
+            assert_eq!(
+                path.breakdown.iter().map(|step| step.cost).sum::<usize>(),
+                path.cost
+            );
+        }
+    }
+
+    #[test]
+    fn a_star_takes_the_teleport_shortcut() {
+        let environmental_conditions = conditions();
+        let map = map(&[
+            "+ O # + + + + +",
+            "+ + # + + + + +",
+            "+ + # + + + + +",
+            "+ + # + + + + +",
+            "+ + # + + + + +",
+            "+ + # + + + + +",
+            "+ + # + + + + O",
+            "+ + + + + + + +",
+        ]);
+        let targets = HashSet::from([(7, 7)]);
+
+        let expected = dijkstra(
+            &environmental_conditions,
+            &map,
+            (0, 0),
+            targets.clone(),
+            &options(SearchAlgorithm::Dijkstra),
+        )
+        .unwrap();
+        let actual = dijkstra(
+            &environmental_conditions,
+            &map,
+            (0, 0),
+            targets,
+            &options(SearchAlgorithm::AStar),
+        )
+        .unwrap();
+
+        assert_eq!(expected.cost, actual.cost);
+        assert!(actual
+            .actions
+            .iter()
+            .any(|action| matches!(action, Action::Teleport((6, 7)))));
+    }
+
+    fn constrained(constraints: Constraints) -> SearchOptions {
+        SearchOptions {
+            constraints,
+            ..SearchOptions::default()
+        }
+    }
+
+    #[test]
+    fn excluded_tiles_are_avoided() {
+        let environmental_conditions = conditions();
+        let map = map(&[". : .", ". . .", ". . ."]);
+        let targets = HashSet::from([(0, 2)]);
+
+        for constraints in [
+            Constraints {
+                excluded_coordinates: HashSet::from([(0, 1)]),
+                ..Constraints::default()
+            },
+            Constraints {
+                excluded_tile_types: HashSet::from([PlainTileType::Sand]),
+                ..Constraints::default()
+            },
+        ] {
+            let path = dijkstra(
+                &environmental_conditions,
+                &map,
+                (0, 0),
+                targets.clone(),
+                &constrained(constraints),
+            )
+            .unwrap();
+
+            assert!(!path.contains((0, 1)));
+            assert_eq!(path.actions.len(), 4);
+        }
+    }
+
+    #[test]
+    fn excluded_tiles_make_the_only_way_unreachable() {
+        let environmental_conditions = conditions();
+        let map = map(&[". : .", "# # #", "# # #"]);
+        let constraints = Constraints {
+            excluded_tile_types: HashSet::from([PlainTileType::Sand]),
+            ..Constraints::default()
+        };
+
+        assert!(matches!(
+            dijkstra(
+                &environmental_conditions,
+                &map,
+                (0, 0),
+                HashSet::from([(0, 2)]),
+                &constrained(constraints),
+            ),
+            Err(TomTomError::Unreachable)
+        ));
+    }
+
+    #[test]
+    fn penalties_change_the_route_but_not_the_cost() {
+        let environmental_conditions = conditions();
+        let map = map(&[". . .", ". . .", ". . ."]);
+        let targets = HashSet::from([(0, 2)]);
+        let penalized = |penalty| Constraints {
+            coordinates_penalties: HashMap::from([((0, 1), penalty)]),
+            ..Constraints::default()
+        };
+
+        let path = dijkstra(
+            &environmental_conditions,
+            &map,
+            (0, 0),
+            targets.clone(),
+            &constrained(penalized(10)),
+        )
+        .unwrap();
+
+        assert!(!path.contains((0, 1)));
+        assert_eq!(path.cost, 4);
+
+        let path = dijkstra(
+            &environmental_conditions,
+            &map,
+            (0, 0),
+            targets,
+            &constrained(penalized(1)),
+        )
+        .unwrap();
+
+        assert!(path.contains((0, 1)));
+        assert_eq!(path.cost, 2);
+        assert_eq!(
+            path.breakdown.iter().map(|step| step.cost).sum::<usize>(),
+            path.cost
+        );
+    }
+
+    /// RainyCostModel costs the go actions like DefaultCostModel in sunny weather, ten times as much when rainy.
+    struct RainyCostModel;
+
+    impl RainyCostModel {
+        fn factor(environmental_conditions: &EnvironmentalConditions) -> usize {
+            match environmental_conditions.get_weather_condition() {
+                WeatherType::Rainy => 10,
+                _ => 1,
             }
         }
     }
 
-    let mut ret = Path::default();
-    ret.cost = usize::MAX;
+    impl CostModel for RainyCostModel {
+        fn go_cost(
+            &self,
+            map: &[Vec<Option<Tile>>],
+            environmental_conditions: &EnvironmentalConditions,
+            source: (usize, usize),
+            direction: Direction,
+        ) -> Result<usize, TomTomError> {
+            DefaultCostModel
+                .go_cost(map, &conditions(), source, direction)
+                .map(|cost| cost * Self::factor(environmental_conditions))
+        }
+
+        fn teleport_cost(
+            &self,
+            map: &[Vec<Option<Tile>>],
+            source: (usize, usize),
+            destination: (usize, usize),
+        ) -> Result<usize, TomTomError> {
+            DefaultCostModel.teleport_cost(map, source, destination)
+        }
+
+        fn min_go_cost(&self, environmental_conditions: &EnvironmentalConditions) -> usize {
+            DefaultCostModel.min_go_cost(&conditions()) * Self::factor(environmental_conditions)
+        }
+    }
+
+    fn rain_from(algorithm: SearchAlgorithm, step: usize) -> SearchOptions {
+        SearchOptions {
+            algorithm,
+            cost_model: Rc::new(RainyCostModel),
+            forecast: Some(Rc::new(RainFrom(step))),
+            ..SearchOptions::default()
+        }
+    }
+
+    #[test]
+    fn the_path_with_fewer_steps_wins_before_the_rain() {
+        let environmental_conditions = conditions();
+        let map = map(&[
+            ". + . . .",
+            ". . . # .",
+            "# # # # .",
+            "# # # # .",
+            "# # # # .",
+        ]);
+        let targets = HashSet::from([(4, 4)]);
+
+        let detour = dijkstra(
+            &environmental_conditions,
+            &map,
+            (0, 0),
+            targets.clone(),
+            &options(SearchAlgorithm::Dijkstra),
+        )
+        .unwrap();
+
+        assert!(!detour.contains((0, 1)));
+        assert_eq!(detour.actions.len(), 10);
 
-    for (target_row, target_col) in targets {
-        if paths[target_row][target_col].cost < ret.cost {
-            ret = paths[target_row][target_col].clone();
+        for algorithm in [SearchAlgorithm::Dijkstra, SearchAlgorithm::AStar] {
+            let path = dijkstra(
+                &environmental_conditions,
+                &map,
+                (0, 0),
+                targets.clone(),
+                &rain_from(algorithm, 6),
+            )
+            .unwrap();
+
+            let options = rain_from(SearchAlgorithm::Dijkstra, 6);
+            let graph = Graph::new(&map, &environmental_conditions, &options);
+            let detour = graph.path((0, 0), detour.actions.iter(), 0).unwrap();
+
+            assert!(path.contains((0, 1)));
+            assert_eq!(path.actions.len(), 8);
+            assert!(path.cost < detour.cost);
+            assert_eq!(
+                path.breakdown.iter().map(|step| step.cost).sum::<usize>(),
+                path.cost
+            );
         }
     }
 
-    if ret.cost == usize::MAX {
-        Err(String::from("Path not found!"))
-    } else {
-        Ok(ret)
+    #[test]
+    fn a_star_returns_the_same_cost_as_dijkstra_with_a_forecast() {
+        let environmental_conditions = conditions();
+        let mut rng = Rng::new(3);
+
+        for _ in 0..200 {
+            let teleports = rng.next(4);
+            let map = random_map(&mut rng, 10, teleports);
+            let source = (rng.next(10), rng.next(10));
+            let targets = HashSet::from([(rng.next(10), rng.next(10))]);
+            let step = rng.next(12);
+
+            let options = rain_from(SearchAlgorithm::AStar, step);
+            assert!(Graph::new(&map, &environmental_conditions, &options).min_go_cost() > 0);
+
+            let expected = dijkstra(
+                &environmental_conditions,
+                &map,
+                source,
+                targets.clone(),
+                &rain_from(SearchAlgorithm::Dijkstra, step),
+            )
+            .map(|path| path.cost);
+            let actual = dijkstra(&environmental_conditions, &map, source, targets, &options)
+                .map(|path| path.cost);
+
+            assert_eq!(expected.ok(), actual.ok());
+        }
     }
 }
@@ -0,0 +1,162 @@
+use std::error::Error;
+use std::fmt::{Display, Formatter};
+
+use robotics_lib::utils::LibError;
+
+/// TomTomError enumerates the errors that can be returned by TomTom.
+///
+/// # Variants:
+/// - MapNotVisible => the robot map is not visible.
+/// - SourceOutOfBounds => the source tile is out of the map bounds.
+/// - OutOfBounds((usize, usize)) => the tile of coordinates (row, col) is out of the map bounds, or a go action from it leaves the map.
+/// - Undiscovered((usize, usize)) => the tile of coordinates (row, col) has not been discovered by the robot.
+/// - NotWalkable((usize, usize)) => the tile of coordinates (row, col) is not walkable.
+/// - NotATeleport((usize, usize)) => the tile of coordinates (row, col) is not an active teleport.
+/// - NotOnSource((usize, usize)) => the robot is on the tile of coordinates (row, col) instead of the source tile of the path.
+/// - NoTargets => no tile matches the request.
+/// - InvalidEncoding(usize) => the encoded actions are invalid at the character of the given byte index.
+/// - Unreachable => no path reaches the targets.
+/// - NotEnoughEnergy { required, available } => the robot has not enough energy to complete the path.
+/// - GoFailed { step, source } => the go interface failed while performing the action of index step, source is the cause.
+/// - TeleportFailed { step, destination, source } => the teleport interface failed while performing the action of index step, source is the cause.
+#[derive(Debug)]
+pub enum TomTomError {
+    MapNotVisible,
+    SourceOutOfBounds,
+    OutOfBounds((usize, usize)),
+    Undiscovered((usize, usize)),
+    NotWalkable((usize, usize)),
+    NotATeleport((usize, usize)),
+    NotOnSource((usize, usize)),
+    NoTargets,
+    InvalidEncoding(usize),
+    Unreachable,
+    NotEnoughEnergy {
+        required: usize,
+        available: usize,
+    },
+    GoFailed {
+        step: usize,
+        source: InterfaceError,
+    },
+    TeleportFailed {
+        step: usize,
+        destination: (usize, usize),
+        source: InterfaceError,
+    },
+}
+
+/// InterfaceError wraps the LibError returned by a robotics_lib interface, so that it can be chained as the source of a TomTomError.
+#[derive(Debug)]
+pub struct InterfaceError(pub LibError);
+
+impl Display for InterfaceError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:?}", self.0)
+    }
+}
+
+impl Error for InterfaceError {}
+
+impl Display for TomTomError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            TomTomError::MapNotVisible => write!(f, "Map not visible!"),
+            TomTomError::SourceOutOfBounds => write!(f, "Source out of bounds!"),
+            TomTomError::OutOfBounds((row, col)) => {
+                write!(f, "Tile ({}, {}) out of bounds!", row, col)
+            }
+            TomTomError::Undiscovered((row, col)) => {
+                write!(f, "Tile ({}, {}) not discovered!", row, col)
+            }
+            TomTomError::NotWalkable((row, col)) => {
+                write!(f, "Tile ({}, {}) not walkable!", row, col)
+            }
+            TomTomError::NotATeleport((row, col)) => {
+                write!(f, "Tile ({}, {}) is not a teleport!", row, col)
+            }
+            TomTomError::NotOnSource((row, col)) => {
+                write!(
+                    f,
+                    "Robot on tile ({}, {}) instead of the path source!",
+                    row, col
+                )
+            }
+            TomTomError::NoTargets => write!(f, "No targets found!"),
+            TomTomError::InvalidEncoding(index) => {
+                write!(f, "Invalid encoding at index {}!", index)
+            }
+            TomTomError::Unreachable => write!(f, "Path not found!"),
+            TomTomError::NotEnoughEnergy {
+                required,
+                available,
+            } => write!(
+                f,
+                "Not enough energy! Required {}, available {}",
+                required, available
+            ),
+            TomTomError::GoFailed { step, source } => {
+                write!(
+                    f,
+                    "Error while calling go interface at step {}: {}",
+                    step, source
+                )
+            }
+            TomTomError::TeleportFailed {
+                step,
+                destination: (row, col),
+                source,
+            } => write!(
+                f,
+                "Error while calling teleport interface to ({}, {}) at step {}: {}",
+                row, col, step, source
+            ),
+        }
+    }
+}
+
+impl Error for TomTomError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            TomTomError::GoFailed { source, .. } | TomTomError::TeleportFailed { source, .. } => {
+                Some(source)
+            }
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn interface_failures_chain_the_lib_error() {
+        let errors = [
+            TomTomError::GoFailed {
+                step: 2,
+                source: InterfaceError(LibError::CannotWalk),
+            },
+            TomTomError::TeleportFailed {
+                step: 0,
+                destination: (1, 2),
+                source: InterfaceError(LibError::NotEnoughEnergy),
+            },
+        ];
+
+        for error in errors.iter() {
+            let source = error
+                .source()
+                .and_then(|source| source.downcast_ref::<InterfaceError>())
+                .expect("the interface error is the source");
+
+            assert!(error.to_string().ends_with(&source.to_string()));
+        }
+
+        assert!(matches!(
+            errors[0].source().unwrap().downcast_ref(),
+            Some(InterfaceError(LibError::CannotWalk))
+        ));
+        assert!(TomTomError::Unreachable.source().is_none());
+    }
+}
@@ -0,0 +1,478 @@
+use std::collections::VecDeque;
+
+use robotics_lib::interface::{go, look_at_sky, robot_map, teleport};
+use robotics_lib::runner::Runnable;
+use robotics_lib::utils::LibError;
+use robotics_lib::world::environmental_conditions::EnvironmentalConditions;
+use robotics_lib::world::{tile::Tile, World};
+
+use crate::error::{InterfaceError, TomTomError};
+use crate::path::{Action, Path};
+use crate::search::SearchOptions;
+use crate::utils::{calculate_action_cost, calculate_actions_costs};
+
+/// ExecutionReport describes the execution of a path by the robot.
+///
+/// # Fields:
+/// - path: Path => last path planned for the execution.
+/// - executed: VecDeque<Action> => actions that have been performed successfully.
+/// - remaining: VecDeque<Action> => actions of the last planned path that have not been performed, starting from the failed one.
+/// - failure: Option<TomTomError> => error that stopped the execution before the destination: GoFailed or TeleportFailed, carrying the index of the failed action
+///   and the LibError wrapped by InterfaceError, the error returned while searching the path again, or NotEnoughEnergy if the robot can't afford the path searched again.
+/// - replans: usize => number of times the path has been planned again during the execution.
+/// - energy_consumed: usize => energy consumed by the performed actions.
+/// - position: (usize, usize) => coordinates (row, col) of the robot at the end of the execution.
+#[derive(Debug)]
+pub struct ExecutionReport {
+    pub path: Path,
+    pub executed: VecDeque<Action>,
+    pub remaining: VecDeque<Action>,
+    pub failure: Option<TomTomError>,
+    pub replans: usize,
+    pub energy_consumed: usize,
+    pub position: (usize, usize),
+}
+
+impl ExecutionReport {
+    /// is_completed returns true if every action of the path has been performed.
+    pub fn is_completed(&self) -> bool {
+        self.failure.is_none()
+    }
+}
+
+/// View is the tiles seen by the robot, centered on its coordinates (row, col), as returned by the go and teleport interfaces.
+pub(crate) type View = (Vec<Vec<Option<Tile>>>, (usize, usize));
+
+/// Driver performs the actions of an execution and observes the robot, so that the execution doesn't depend on the world.
+pub(crate) trait Driver {
+    fn position(&self) -> (usize, usize);
+    fn energy_level(&self) -> usize;
+    fn has_enough_energy(&self, cost: usize) -> bool;
+    fn map(&self) -> Option<Vec<Vec<Option<Tile>>>>;
+    fn environmental_conditions(&self) -> EnvironmentalConditions;
+    /// perform returns the view of the robot after the action.
+    fn perform(&mut self, action: &Action) -> Result<View, LibError>;
+    fn plan(&mut self) -> Result<Path, TomTomError>;
+}
+
+/// WorldDriver performs the actions with the go and teleport interfaces, planning again with the given closure.
+struct WorldDriver<'a, R: Runnable, P: FnMut(&R, &World) -> Result<Path, TomTomError>> {
+    robot: &'a mut R,
+    world: &'a mut World,
+    plan: P,
+}
+
+impl<R: Runnable, P: FnMut(&R, &World) -> Result<Path, TomTomError>> Driver
+    for WorldDriver<'_, R, P>
+{
+    fn position(&self) -> (usize, usize) {
+        (
+            self.robot.get_coordinate().get_row(),
+            self.robot.get_coordinate().get_col(),
+        )
+    }
+
+    fn energy_level(&self) -> usize {
+        self.robot.get_energy().get_energy_level()
+    }
+
+    fn has_enough_energy(&self, cost: usize) -> bool {
+        self.robot.get_energy().has_enough_energy(cost)
+    }
+
+    fn map(&self) -> Option<Vec<Vec<Option<Tile>>>> {
+        robot_map(self.world)
+    }
+
+    fn environmental_conditions(&self) -> EnvironmentalConditions {
+        look_at_sky(self.world)
+    }
+
+    fn perform(&mut self, action: &Action) -> Result<View, LibError> {
+        match action {
+            Action::Go(direction) => go(self.robot, self.world, direction.clone()),
+            Action::Teleport(destination) => teleport(self.robot, self.world, *destination),
+        }
+    }
+
+    fn plan(&mut self) -> Result<Path, TomTomError> {
+        (self.plan)(self.robot, self.world)
+    }
+}
+
+pub(crate) fn execute<R: Runnable>(
+    robot: &mut R,
+    world: &mut World,
+    path: Path,
+    options: &SearchOptions,
+    plan: impl FnMut(&R, &World) -> Result<Path, TomTomError>,
+) -> ExecutionReport {
+    run(&mut WorldDriver { robot, world, plan }, path, options)
+}
+
+/// run performs the actions of the path, planning the path again when the cost or walkability of the next action changed,
+/// up to options.replan_limit times. A planned path the robot can't afford stops the execution with NotEnoughEnergy.
+/// The robot map is read once per planned path and then kept up to date with the tiles seen after every action.
+pub(crate) fn run(
+    driver: &mut impl Driver,
+    mut path: Path,
+    options: &SearchOptions,
+) -> ExecutionReport {
+    let initial_energy = driver.energy_level();
+
+    let mut map = match options.replan_limit {
+        0 => None,
+        _ => driver.map(),
+    };
+    let mut planned_costs = planned_costs(driver, options, map.as_deref(), &path);
+    let mut executed = VecDeque::new();
+    let mut remaining = path.actions.clone();
+    let mut failure = None;
+    let mut replans = 0;
+    let mut step = 0;
+    let mut index = 0;
+
+    while let Some(action) = remaining.front() {
+        let position = driver.position();
+
+        if replans < options.replan_limit {
+            if let Some(known) = map.as_deref() {
+                let cost = calculate_action_cost(
+                    options.cost_model.as_ref(),
+                    &driver.environmental_conditions(),
+                    known,
+                    position,
+                    action,
+                )
+                .ok();
+
+                if cost != planned_costs[index] {
+                    match driver.plan() {
+                        Ok(new_path) => {
+                            replans += 1;
+                            map = driver.map();
+                            planned_costs =
+                                self::planned_costs(driver, options, map.as_deref(), &new_path);
+                            remaining = new_path.actions.clone();
+                            index = 0;
+
+                            if !driver.has_enough_energy(new_path.cost) {
+                                failure = Some(TomTomError::NotEnoughEnergy {
+                                    required: new_path.cost,
+                                    available: driver.energy_level(),
+                                });
+                                path = new_path;
+                                break;
+                            }
+
+                            path = new_path;
+                            continue;
+                        }
+                        Err(e) => {
+                            failure = Some(e);
+                            break;
+                        }
+                    }
+                }
+            }
+        }
+
+        let result = driver.perform(action).map_err(|e| match action {
+            Action::Go(_) => TomTomError::GoFailed {
+                step,
+                source: InterfaceError(e),
+            },
+            Action::Teleport(destination) => TomTomError::TeleportFailed {
+                step,
+                destination: *destination,
+                source: InterfaceError(e),
+            },
+        });
+
+        match result {
+            Err(e) => {
+                failure = Some(e);
+                break;
+            }
+            Ok((view, position)) => {
+                if let Some(known) = map.as_mut() {
+                    update_map(known, &view, position);
+                }
+            }
+        }
+
+        executed.push_back(remaining.pop_front().unwrap());
+        step += 1;
+        index += 1;
+    }
+
+    ExecutionReport {
+        path,
+        executed,
+        remaining,
+        failure,
+        replans,
+        energy_consumed: initial_energy.saturating_sub(driver.energy_level()),
+        position: driver.position(),
+    }
+}
+
+fn planned_costs(
+    driver: &impl Driver,
+    options: &SearchOptions,
+    map: Option<&[Vec<Option<Tile>>]>,
+    path: &Path,
+) -> Vec<Option<usize>> {
+    match map {
+        None => vec![None; path.actions.len()],
+        Some(map) => calculate_actions_costs(
+            options.cost_model.as_ref(),
+            &driver.environmental_conditions(),
+            map,
+            path.source,
+            &path.actions,
+        ),
+    }
+}
+
+/// update_map copies the discovered tiles of the view, centered on the tile of coordinates (row, col), into the map.
+fn update_map(map: &mut [Vec<Option<Tile>>], view: &[Vec<Option<Tile>>], position: (usize, usize)) {
+    let size = map.len();
+    let offset = view.len() / 2;
+
+    for (i, tiles) in view.iter().enumerate() {
+        for (j, tile) in tiles.iter().enumerate() {
+            let (row, col) = match (
+                (position.0 + i).checked_sub(offset),
+                (position.1 + j).checked_sub(offset),
+            ) {
+                (Some(row), Some(col)) if row < size && col < size => (row, col),
+                _ => continue,
+            };
+
+            if let Some(tile) = tile {
+                map[row][col] = Some(tile.clone());
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::cell::Cell;
+    use std::collections::HashSet;
+
+    use robotics_lib::world::tile::TileType;
+
+    use super::*;
+    use crate::dijkstra::dijkstra;
+    use crate::test_utils::{conditions, map, tile};
+    use crate::utils::get_action_destination;
+
+    /// MockDriver moves the robot on the map, discovering the changes scheduled after the given number of performed actions.
+    struct MockDriver {
+        map: Vec<Vec<Option<Tile>>>,
+        position: (usize, usize),
+        energy: usize,
+        destination: (usize, usize),
+        options: SearchOptions,
+        changes: Vec<(usize, (usize, usize), Tile)>,
+        performed: usize,
+        map_reads: Cell<usize>,
+    }
+
+    impl MockDriver {
+        fn new(energy: usize, options: SearchOptions) -> MockDriver {
+            MockDriver {
+                map: map(&[
+                    ". . . . .",
+                    ". # # # .",
+                    ". . . . .",
+                    "# # # # #",
+                    "# # # # #",
+                ]),
+                position: (0, 0),
+                energy,
+                destination: (0, 4),
+                options,
+                changes: Vec::new(),
+                performed: 0,
+                map_reads: Cell::new(0),
+            }
+        }
+    }
+
+    impl Driver for MockDriver {
+        fn position(&self) -> (usize, usize) {
+            self.position
+        }
+
+        fn energy_level(&self) -> usize {
+            self.energy
+        }
+
+        fn has_enough_energy(&self, cost: usize) -> bool {
+            self.energy >= cost
+        }
+
+        fn map(&self) -> Option<Vec<Vec<Option<Tile>>>> {
+            self.map_reads.set(self.map_reads.get() + 1);
+            Some(self.map.clone())
+        }
+
+        fn environmental_conditions(&self) -> EnvironmentalConditions {
+            conditions()
+        }
+
+        fn perform(&mut self, action: &Action) -> Result<View, LibError> {
+            let cost = calculate_action_cost(
+                self.options.cost_model.as_ref(),
+                &conditions(),
+                &self.map,
+                self.position,
+                action,
+            )
+            .map_err(|_| LibError::CannotWalk)?;
+
+            if cost > self.energy {
+                return Err(LibError::NotEnoughEnergy);
+            }
+
+            self.energy -= cost;
+            self.position = get_action_destination(self.map.len(), self.position, action).unwrap();
+            self.performed += 1;
+
+            for (after, (row, col), tile) in self.changes.iter() {
+                if *after == self.performed {
+                    self.map[*row][*col] = Some(tile.clone());
+                }
+            }
+
+            let (row, col) = self.position;
+            let view = (0..3)
+                .map(|i| {
+                    (0..3)
+                        .map(|j| {
+                            let row = (row + i).checked_sub(1)?;
+                            let col = (col + j).checked_sub(1)?;
+                            self.map.get(row)?.get(col)?.clone()
+                        })
+                        .collect()
+                })
+                .collect();
+
+            Ok((view, self.position))
+        }
+
+        fn plan(&mut self) -> Result<Path, TomTomError> {
+            dijkstra(
+                &conditions(),
+                &self.map,
+                self.position,
+                HashSet::from([self.destination]),
+                &self.options,
+            )
+        }
+    }
+
+    fn replan_options(replan_limit: usize) -> SearchOptions {
+        SearchOptions {
+            replan_limit,
+            ..SearchOptions::default()
+        }
+    }
+
+    #[test]
+    fn replans_around_a_discovered_wall() {
+        let mut driver = MockDriver::new(1000, replan_options(1));
+        driver.changes.push((1, (0, 2), tile(TileType::Wall)));
+
+        let path = driver.plan().unwrap();
+        let first_cost = path.breakdown[0].cost;
+        let report = run(&mut driver, path, &replan_options(1));
+
+        assert!(report.is_completed());
+        assert_eq!(report.replans, 1);
+        assert_eq!(report.position, (0, 4));
+        assert_eq!(report.executed.len(), 1 + report.path.actions.len());
+        assert_eq!(report.energy_consumed, first_cost + report.path.cost);
+    }
+
+    #[test]
+    fn reads_the_map_once_per_planned_path() {
+        let mut driver = MockDriver::new(1000, replan_options(3));
+        let path = driver.plan().unwrap();
+        let report = run(&mut driver, path, &replan_options(3));
+
+        assert!(report.is_completed());
+        assert_eq!(report.executed.len(), 4);
+        assert_eq!(driver.map_reads.get(), 1);
+
+        let mut driver = MockDriver::new(1000, replan_options(3));
+        driver.changes.push((1, (0, 2), tile(TileType::Wall)));
+
+        let path = driver.plan().unwrap();
+        let report = run(&mut driver, path, &replan_options(3));
+
+        assert!(report.is_completed());
+        assert_eq!(report.replans, 1);
+        assert_eq!(driver.map_reads.get(), 2);
+
+        let mut driver = MockDriver::new(1000, replan_options(0));
+        let path = driver.plan().unwrap();
+        run(&mut driver, path, &replan_options(0));
+
+        assert_eq!(driver.map_reads.get(), 0);
+    }
+
+    #[test]
+    fn stops_replanning_at_the_replan_limit() {
+        let mut driver = MockDriver::new(1000, replan_options(0));
+        driver.changes.push((1, (0, 2), tile(TileType::Wall)));
+
+        let path = driver.plan().unwrap();
+        let report = run(&mut driver, path, &replan_options(0));
+
+        assert_eq!(report.replans, 0);
+        assert_eq!(report.position, (0, 1));
+        assert!(matches!(
+            report.failure,
+            Some(TomTomError::GoFailed { step: 1, .. })
+        ));
+
+        let mut driver = MockDriver::new(1000, replan_options(1));
+        driver.changes.push((1, (0, 2), tile(TileType::Wall)));
+        driver.changes.push((3, (2, 1), tile(TileType::Wall)));
+
+        let path = driver.plan().unwrap();
+        let report = run(&mut driver, path, &replan_options(1));
+
+        assert_eq!(report.replans, 1);
+        assert_eq!(report.position, (2, 0));
+        assert!(matches!(
+            report.failure,
+            Some(TomTomError::GoFailed { step: 4, .. })
+        ));
+    }
+
+    #[test]
+    fn fails_when_the_replanned_path_is_not_affordable() {
+        let path = MockDriver::new(0, replan_options(1)).plan().unwrap();
+        let energy = path.cost + path.breakdown[0].cost;
+
+        let mut driver = MockDriver::new(energy, replan_options(1));
+        driver.changes.push((1, (0, 2), tile(TileType::Wall)));
+
+        let report = run(&mut driver, path, &replan_options(1));
+
+        assert_eq!(report.replans, 1);
+        assert_eq!(report.position, (0, 1));
+        assert_eq!(report.executed.len(), 1);
+        assert_eq!(report.remaining.len(), report.path.actions.len());
+        assert!(matches!(
+            report.failure,
+            Some(TomTomError::NotEnoughEnergy { required, available })
+                if required == report.path.cost && available == driver.energy
+        ));
+    }
+}
@@ -0,0 +1,322 @@
+use std::collections::HashSet;
+use std::ops::RangeInclusive;
+use std::rc::Rc;
+
+use robotics_lib::world::environmental_conditions::EnvironmentalConditions;
+use robotics_lib::world::tile::{Content, Tile};
+
+use crate::dijkstra::dijkstra;
+use crate::error::TomTomError;
+use crate::path::Path;
+use crate::plain::{PlainContent, PlainTileType};
+use crate::search::SearchOptions;
+use crate::utils::get_targets;
+
+/// TilePredicate is a closure called with a tile and its coordinates (row, col), returning true if the tile is matched.
+pub type TilePredicate = Rc<dyn Fn(&Tile, (usize, usize)) -> bool>;
+
+/// TileFilter describes the tiles to be matched as targets, composing conditions over the tile and its coordinates.
+///
+/// # Variants:
+/// - TileTypes(HashSet<PlainTileType>) => matches the tiles of one of the tile types.
+/// - Contents(HashSet<PlainContent>) => matches the tiles having one of the contents.
+/// - ContentQuantity(PlainContent, RangeInclusive<usize>) => matches the tiles having the content with a quantity in the range,
+///   e.g. ContentQuantity(PlainContent::Coin, 3..=usize::MAX) matches Coin(n) with n >= 3 (see content_quantity).
+/// - Elevation(RangeInclusive<usize>) => matches the tiles with an elevation in the range.
+/// - Coordinates(HashSet<(usize, usize)>) => matches the tiles of the coordinates (row, col).
+/// - All(Vec<TileFilter>) => matches the tiles matched by every filter (every tile if empty).
+/// - Any(Vec<TileFilter>) => matches the tiles matched by at least one filter (no tile if empty).
+/// - Not(Box<TileFilter>) => matches the tiles not matched by the filter.
+/// - Custom(TilePredicate) => matches the tiles for which the closure returns true.
+#[derive(Clone)]
+pub enum TileFilter {
+    TileTypes(HashSet<PlainTileType>),
+    Contents(HashSet<PlainContent>),
+    ContentQuantity(PlainContent, RangeInclusive<usize>),
+    Elevation(RangeInclusive<usize>),
+    Coordinates(HashSet<(usize, usize)>),
+    All(Vec<TileFilter>),
+    Any(Vec<TileFilter>),
+    Not(Box<TileFilter>),
+    Custom(TilePredicate),
+}
+
+impl TileFilter {
+    /// tile returns the filter matching the tiles of the optional tile type having the optional content (every tile if both are None).
+    pub fn tile(tile_type: Option<PlainTileType>, content: Option<PlainContent>) -> TileFilter {
+        TileFilter::All(
+            tile_type
+                .map(|tile_type| TileFilter::TileTypes(HashSet::from([tile_type])))
+                .into_iter()
+                .chain(content.map(|content| TileFilter::Contents(HashSet::from([content]))))
+                .collect(),
+        )
+    }
+
+    /// matches returns true if the tile of coordinates (row, col) is matched by the filter.
+    pub fn matches(&self, tile: &Tile, coordinates: (usize, usize)) -> bool {
+        match self {
+            TileFilter::TileTypes(tile_types) => {
+                tile_types.contains(&PlainTileType::from(&tile.tile_type))
+            }
+            TileFilter::Contents(contents) => contents.contains(&PlainContent::from(&tile.content)),
+            TileFilter::ContentQuantity(content, quantity) => {
+                content.eq_content(&tile.content)
+                    && quantity.contains(&content_quantity(&tile.content))
+            }
+            TileFilter::Elevation(elevation) => elevation.contains(&tile.elevation),
+            TileFilter::Coordinates(coordinates_set) => coordinates_set.contains(&coordinates),
+            TileFilter::All(filters) => filters
+                .iter()
+                .all(|filter| filter.matches(tile, coordinates)),
+            TileFilter::Any(filters) => filters
+                .iter()
+                .any(|filter| filter.matches(tile, coordinates)),
+            TileFilter::Not(filter) => !filter.matches(tile, coordinates),
+            TileFilter::Custom(predicate) => predicate(tile, coordinates),
+        }
+    }
+
+    /// and returns the filter matching the tiles matched by both this filter and the other filter.
+    pub fn and(self, other: TileFilter) -> TileFilter {
+        match self {
+            TileFilter::All(mut filters) => {
+                filters.push(other);
+                TileFilter::All(filters)
+            }
+            filter => TileFilter::All(vec![filter, other]),
+        }
+    }
+
+    /// or returns the filter matching the tiles matched by this filter or by the other filter.
+    pub fn or(self, other: TileFilter) -> TileFilter {
+        match self {
+            TileFilter::Any(mut filters) => {
+                filters.push(other);
+                TileFilter::Any(filters)
+            }
+            filter => TileFilter::Any(vec![filter, other]),
+        }
+    }
+
+    /// excluding returns the filter matching the tiles matched by this filter, except the tiles of the given coordinates (row, col).
+    pub fn excluding(self, coordinates: impl IntoIterator<Item = (usize, usize)>) -> TileFilter {
+        self.and(TileFilter::Not(Box::new(TileFilter::Coordinates(
+            coordinates.into_iter().collect(),
+        ))))
+    }
+}
+
+/// content_quantity returns the quantity of the content: the parameter of the contents having a quantity,
+/// the start of the range for Bin, Crate and Bank, 0 for the other contents.
+pub fn content_quantity(content: &Content) -> usize {
+    match content {
+        Content::Rock(quantity)
+        | Content::Tree(quantity)
+        | Content::Garbage(quantity)
+        | Content::Coin(quantity)
+        | Content::Water(quantity)
+        | Content::Market(quantity)
+        | Content::Fish(quantity)
+        | Content::Bush(quantity)
+        | Content::JollyBlock(quantity) => *quantity,
+        Content::Bin(range) | Content::Crate(range) | Content::Bank(range) => range.start,
+        Content::Fire | Content::Building | Content::Scarecrow | Content::None => 0,
+    }
+}
+
+pub(crate) fn get_matching_tiles(
+    map: &[Vec<Option<Tile>>],
+    filter: &TileFilter,
+) -> Vec<(usize, usize)> {
+    let mut ret = Vec::new();
+
+    for (row, tiles) in map.iter().enumerate() {
+        for (col, tile) in tiles.iter().enumerate() {
+            if let Some(tile) = tile {
+                if filter.matches(tile, (row, col)) {
+                    ret.push((row, col));
+                }
+            }
+        }
+    }
+
+    ret
+}
+
+/// get_path_to_matching returns the path to the nearest tile matched by the filter, or to the nearest tile adjacent to a matched tile.
+pub(crate) fn get_path_to_matching(
+    environmental_conditions: &EnvironmentalConditions,
+    map: &[Vec<Option<Tile>>],
+    source: (usize, usize),
+    adjacent: bool,
+    filter: &TileFilter,
+    options: &SearchOptions,
+) -> Result<Path, TomTomError> {
+    let targets = get_targets(map, adjacent, get_matching_tiles(map, filter));
+
+    dijkstra(environmental_conditions, map, source, targets, options)
+}
+
+#[cfg(test)]
+mod tests {
+    use robotics_lib::world::tile::TileType;
+
+    use super::*;
+    use crate::test_utils::{conditions, map};
+
+    /// fixture returns a map with coins, a bin, a crate and a bank, elevated in the last row and undiscovered in the center.
+    fn fixture() -> Vec<Vec<Option<Tile>>> {
+        let mut map = map(&[". . :", ": ? .", "O . ."]);
+        let mut set = |(row, col): (usize, usize), content: Content, elevation: usize| {
+            let tile = map[row][col].as_mut().unwrap();
+            tile.content = content;
+            tile.elevation = elevation;
+        };
+
+        set((0, 1), Content::Coin(2), 0);
+        set((0, 2), Content::Coin(5), 0);
+        set((1, 0), Content::Bin(3..6), 0);
+        set((1, 2), Content::Crate(1..4), 0);
+        set((2, 1), Content::Bank(0..10), 2);
+        set((2, 2), Content::None, 5);
+
+        map
+    }
+
+    fn matching(filter: TileFilter) -> Vec<(usize, usize)> {
+        get_matching_tiles(&fixture(), &filter)
+    }
+
+    #[test]
+    fn tile_matches_the_optional_tile_type_and_content() {
+        let mut map = map(&[". . :", ": ? .", "O . ."]);
+        map[0][1].as_mut().unwrap().content = Content::Coin(2);
+        map[0][2].as_mut().unwrap().content = Content::Coin(5);
+        map[2][0].as_mut().unwrap().tile_type = TileType::Teleport(false);
+
+        let cases = [
+            (
+                None,
+                None,
+                vec![
+                    (0, 0),
+                    (0, 1),
+                    (0, 2),
+                    (1, 0),
+                    (1, 2),
+                    (2, 0),
+                    (2, 1),
+                    (2, 2),
+                ],
+            ),
+            (Some(PlainTileType::Sand), None, vec![(0, 2), (1, 0)]),
+            (Some(PlainTileType::Teleport), None, vec![(2, 0)]),
+            (None, Some(PlainContent::Coin), vec![(0, 1), (0, 2)]),
+            (
+                Some(PlainTileType::Grass),
+                Some(PlainContent::Coin),
+                vec![(0, 1)],
+            ),
+            (Some(PlainTileType::Hill), None, vec![]),
+        ];
+
+        for (tile_type, content, expected) in cases {
+            assert_eq!(
+                get_matching_tiles(&map, &TileFilter::tile(tile_type, content)),
+                expected,
+                "{tile_type:?} {content:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn content_quantity_matches_the_quantity_or_the_start_of_the_range() {
+        let quantity = |content, range| matching(TileFilter::ContentQuantity(content, range));
+
+        assert_eq!(quantity(PlainContent::Coin, 3..=usize::MAX), vec![(0, 2)]);
+        assert_eq!(quantity(PlainContent::Coin, 0..=5), vec![(0, 1), (0, 2)]);
+        assert_eq!(quantity(PlainContent::Bin, 3..=3), vec![(1, 0)]);
+        assert_eq!(quantity(PlainContent::Bin, 4..=5), vec![]);
+        assert_eq!(quantity(PlainContent::Crate, 1..=1), vec![(1, 2)]);
+        assert_eq!(quantity(PlainContent::Bank, 0..=0), vec![(2, 1)]);
+        assert_eq!(quantity(PlainContent::Bank, 1..=usize::MAX), vec![]);
+        assert_eq!(quantity(PlainContent::Rock, 0..=usize::MAX), vec![]);
+    }
+
+    #[test]
+    fn elevation_matches_the_range() {
+        assert_eq!(matching(TileFilter::Elevation(1..=4)), vec![(2, 1)]);
+        assert_eq!(matching(TileFilter::Elevation(2..=5)), vec![(2, 1), (2, 2)]);
+        assert_eq!(matching(TileFilter::Elevation(6..=9)), vec![]);
+    }
+
+    #[test]
+    fn coordinates_match_the_discovered_tiles_only() {
+        assert_eq!(
+            matching(TileFilter::Coordinates(HashSet::from([
+                (0, 0),
+                (1, 1),
+                (2, 2),
+                (5, 5)
+            ]))),
+            vec![(0, 0), (2, 2)]
+        );
+    }
+
+    #[test]
+    fn combinators_compose_the_filters() {
+        let coins = || TileFilter::Contents(HashSet::from([PlainContent::Coin]));
+        let sand = || TileFilter::TileTypes(HashSet::from([PlainTileType::Sand]));
+
+        assert_eq!(matching(TileFilter::All(vec![])).len(), 8);
+        assert_eq!(matching(TileFilter::Any(vec![])), vec![]);
+        assert_eq!(matching(coins().and(sand())), vec![(0, 2)]);
+        assert_eq!(matching(coins().or(sand())), vec![(0, 1), (0, 2), (1, 0)]);
+        assert_eq!(
+            matching(TileFilter::Not(Box::new(coins().or(sand())))),
+            vec![(0, 0), (1, 2), (2, 0), (2, 1), (2, 2)]
+        );
+        assert_eq!(matching(coins().excluding([(0, 1)])), vec![(0, 2)]);
+        assert_eq!(matching(coins().excluding([(0, 1), (0, 2)])), vec![]);
+    }
+
+    #[test]
+    fn custom_is_called_with_the_tile_and_its_coordinates() {
+        let diagonal = TileFilter::Custom(Rc::new(|tile, (row, col)| {
+            row == col && tile.content == Content::None
+        }));
+
+        assert_eq!(matching(diagonal), vec![(0, 0), (2, 2)]);
+    }
+
+    #[test]
+    fn path_to_matching_reaches_the_nearest_matched_tile() {
+        let map = fixture();
+        let path = |adjacent, filter: TileFilter| {
+            get_path_to_matching(
+                &conditions(),
+                &map,
+                (2, 0),
+                adjacent,
+                &filter,
+                &SearchOptions::default(),
+            )
+        };
+
+        let coins = TileFilter::Contents(HashSet::from([PlainContent::Coin]));
+        assert_eq!(path(false, coins.clone()).unwrap().destination, (0, 1));
+
+        let rich = TileFilter::ContentQuantity(PlainContent::Coin, 5..=usize::MAX);
+        assert_eq!(path(false, rich.clone()).unwrap().destination, (0, 2));
+
+        let adjacent = path(true, rich).unwrap();
+        assert_eq!(adjacent.destination, (0, 1));
+        assert!(!adjacent.contains((0, 2)));
+
+        assert!(matches!(
+            path(false, TileFilter::Any(vec![])),
+            Err(TomTomError::NoTargets)
+        ));
+    }
+}
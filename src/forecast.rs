@@ -0,0 +1,151 @@
+use robotics_lib::world::environmental_conditions::{EnvironmentalConditions, WeatherType};
+
+/// MINUTES_PER_DAY is the number of minutes after which the time of day starts again from midnight.
+const MINUTES_PER_DAY: usize = 24 * 60;
+
+/// Forecast describes the environmental conditions expected while the robot performs the actions of a path.
+pub trait Forecast {
+    /// conditions returns the environmental conditions expected while performing the action of the given step.
+    ///
+    /// # Arguments
+    /// - step: usize => index of the action in the path, 0 being the first action performed from the robot's tile.
+    ///
+    /// # Return
+    /// - Option<EnvironmentalConditions> => Some(conditions) returns the expected conditions, None means the current conditions are used.
+    fn conditions(&self, step: usize) -> Option<EnvironmentalConditions>;
+
+    /// horizon returns the last step described by the forecast: the actions after it are costed with the conditions of the horizon.
+    /// The search keeps a state for every tile and every step up to the horizon, so that it returns the path of smallest cost
+    /// under the forecast, using horizon + 1 times the memory of a search without forecast.
+    ///
+    /// # Return
+    /// - usize => index of the last step forecast.
+    fn horizon(&self) -> usize;
+}
+
+/// DayCycleForecast simulates the progression of the world's environmental conditions: the time of day advances
+/// by time_progression_minutes every tick and the weather follows the forecast one day at a time, starting again from the first day.
+///
+/// # Fields:
+/// - weather_forecast: Vec<WeatherType> => weather of each day, the same forecast used to generate the world.
+/// - time_progression_minutes: u8 => minutes the time of day advances every tick, the same used to generate the world.
+/// - minutes: usize => minutes elapsed at the current tick since the midnight of the first day of the forecast.
+/// - actions_per_tick: usize => number of actions the robot performs every tick (0 is considered 1).
+/// - horizon: usize => last step forecast, see Forecast::horizon.
+#[derive(Debug, Clone)]
+pub struct DayCycleForecast {
+    pub weather_forecast: Vec<WeatherType>,
+    pub time_progression_minutes: u8,
+    pub minutes: usize,
+    pub actions_per_tick: usize,
+    pub horizon: usize,
+}
+
+impl DayCycleForecast {
+    /// new returns the forecast starting from the current day of the forecast and the current time of day of the world, read with look_at_sky.
+    /// The world doesn't expose the day of the forecast it has reached, so the robot counts it, e.g. from the time of day going back to midnight.
+    /// Midnight is assumed if the time of day can't be read.
+    ///
+    /// # Arguments
+    /// - environmental_conditions: &EnvironmentalConditions => current environmental conditions, e.g. look_at_sky(world).
+    /// - weather_forecast: Vec<WeatherType> => weather of each day, the same forecast used to generate the world.
+    /// - day: usize => index of the current day in the weather forecast.
+    /// - time_progression_minutes: u8 => minutes the time of day advances every tick, the same used to generate the world.
+    /// - actions_per_tick: usize => number of actions the robot performs every tick (0 is considered 1).
+    /// - horizon: usize => last step forecast, see Forecast::horizon.
+    ///
+    /// # Return
+    /// - DayCycleForecast => the forecast.
+    pub fn new(
+        environmental_conditions: &EnvironmentalConditions,
+        weather_forecast: Vec<WeatherType>,
+        day: usize,
+        time_progression_minutes: u8,
+        actions_per_tick: usize,
+        horizon: usize,
+    ) -> DayCycleForecast {
+        DayCycleForecast {
+            weather_forecast,
+            time_progression_minutes,
+            minutes: day * MINUTES_PER_DAY + minutes_of_day(environmental_conditions).unwrap_or(0),
+            actions_per_tick,
+            horizon,
+        }
+    }
+}
+
+/// minutes_of_day returns the minutes elapsed since midnight, read from the time of day formatted as HH:MM.
+fn minutes_of_day(environmental_conditions: &EnvironmentalConditions) -> Option<usize> {
+    let time = environmental_conditions.get_time_of_day_string();
+    let (hours, minutes) = time.split_once(':')?;
+
+    Some(hours.trim().parse::<usize>().ok()? * 60 + minutes.trim().parse::<usize>().ok()?)
+}
+
+impl Forecast for DayCycleForecast {
+    fn conditions(&self, step: usize) -> Option<EnvironmentalConditions> {
+        if self.weather_forecast.is_empty() {
+            return None;
+        }
+
+        let ticks = step / self.actions_per_tick.max(1);
+        let minutes = self.minutes + ticks * self.time_progression_minutes as usize;
+
+        let weather =
+            self.weather_forecast[(minutes / MINUTES_PER_DAY) % self.weather_forecast.len()];
+        let hour = (minutes % MINUTES_PER_DAY) / 60;
+
+        EnvironmentalConditions::new(&[weather], self.time_progression_minutes, hour as u8).ok()
+    }
+
+    fn horizon(&self) -> usize {
+        self.horizon
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn new_starts_from_the_current_day_and_time() {
+        let environmental_conditions =
+            EnvironmentalConditions::new(&[WeatherType::Rainy], 15, 9).unwrap();
+
+        let weather_forecast = vec![WeatherType::Rainy, WeatherType::Sunny, WeatherType::Rainy];
+
+        let forecast = DayCycleForecast::new(
+            &environmental_conditions,
+            weather_forecast.clone(),
+            2,
+            15,
+            1,
+            10,
+        );
+        assert_eq!(forecast.minutes, 2 * MINUTES_PER_DAY + 9 * 60);
+        assert_eq!(forecast.horizon(), 10);
+
+        let forecast =
+            DayCycleForecast::new(&environmental_conditions, weather_forecast, 0, 15, 1, 10);
+        assert_eq!(forecast.minutes, 9 * 60);
+    }
+
+    #[test]
+    fn conditions_follow_the_day_cycle() {
+        let forecast = DayCycleForecast {
+            weather_forecast: vec![WeatherType::Sunny, WeatherType::Rainy],
+            time_progression_minutes: 60,
+            minutes: 22 * 60,
+            actions_per_tick: 2,
+            horizon: 60,
+        };
+
+        let weather = |step| forecast.conditions(step).unwrap().get_weather_condition();
+
+        assert_eq!(weather(0), WeatherType::Sunny);
+        assert_eq!(weather(3), WeatherType::Sunny);
+        assert_eq!(weather(4), WeatherType::Rainy);
+        assert_eq!(weather(51), WeatherType::Rainy);
+        assert_eq!(weather(52), WeatherType::Sunny);
+    }
+}
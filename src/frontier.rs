@@ -0,0 +1,199 @@
+use std::cmp::Ordering;
+use std::collections::HashSet;
+
+use robotics_lib::world::environmental_conditions::EnvironmentalConditions;
+use robotics_lib::world::tile::Tile;
+
+use crate::dijkstra::{search, Graph, SearchTree};
+use crate::error::TomTomError;
+use crate::path::Path;
+use crate::search::SearchOptions;
+
+/// Frontier describes a discovered walkable tile next to undiscovered tiles.
+///
+/// # Fields:
+/// - coordinates: (usize, usize) => coordinates (row, col) of the tile.
+/// - cost: usize => energy cost of the path reaching the tile.
+/// - unknown_neighbours: usize => number of undiscovered tiles among the 8 surrounding ones, i.e. the tiles revealed by robot_view on it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Frontier {
+    pub coordinates: (usize, usize),
+    pub cost: usize,
+    pub unknown_neighbours: usize,
+}
+
+fn count_unknown_neighbours(map: &[Vec<Option<Tile>>], tile: (usize, usize)) -> usize {
+    let (row, col) = tile;
+    let size = map.len();
+
+    let rows = row.saturating_sub(1)..=(row + 1).min(size - 1);
+    let cols = col.saturating_sub(1)..=(col + 1).min(size - 1);
+
+    map[rows.clone()]
+        .iter()
+        .zip(rows)
+        .flat_map(|(tiles, neighbour_row)| {
+            tiles[cols.clone()]
+                .iter()
+                .zip(cols.clone())
+                .map(move |(neighbour, neighbour_col)| ((neighbour_row, neighbour_col), neighbour))
+        })
+        .filter(|(neighbour, tile_option)| *neighbour != tile && tile_option.is_none())
+        .count()
+}
+
+/// search_frontiers returns the search tree from the source tile and the reachable frontiers other than the source tile,
+/// ranked by search distance per unknown neighbour (smallest first), then by search distance.
+fn search_frontiers(
+    graph: &Graph,
+    map: &[Vec<Option<Tile>>],
+    source: (usize, usize),
+) -> Result<(SearchTree, Vec<Frontier>), TomTomError> {
+    let tree = search(graph, source, &HashSet::new())?;
+
+    let mut frontiers = Vec::new();
+
+    for (row, tiles) in map.iter().enumerate() {
+        for (col, tile) in tiles.iter().enumerate() {
+            let walkable = match tile {
+                None => false,
+                Some(tile) => tile.tile_type.properties().walk(),
+            };
+
+            if !walkable || (row, col) == source {
+                continue;
+            }
+
+            let unknown_neighbours = count_unknown_neighbours(map, (row, col));
+
+            if unknown_neighbours == 0 {
+                continue;
+            }
+
+            if let (Some(distance), Some(cost)) = (tree.distance((row, col)), tree.cost((row, col)))
+            {
+                frontiers.push((
+                    distance,
+                    Frontier {
+                        coordinates: (row, col),
+                        cost,
+                        unknown_neighbours,
+                    },
+                ));
+            }
+        }
+    }
+
+    frontiers.sort_by(|(a_distance, a), (b_distance, b)| {
+        let a_rank = *a_distance as u128 * b.unknown_neighbours as u128;
+        let b_rank = *b_distance as u128 * a.unknown_neighbours as u128;
+
+        match a_rank.cmp(&b_rank) {
+            Ordering::Equal => a_distance.cmp(b_distance),
+            ordering => ordering,
+        }
+    });
+
+    Ok((
+        tree,
+        frontiers
+            .into_iter()
+            .map(|(_, frontier)| frontier)
+            .collect(),
+    ))
+}
+
+/// get_frontiers returns the reachable frontiers from the source tile, ranked as in search_frontiers.
+pub(crate) fn get_frontiers(
+    environmental_conditions: &EnvironmentalConditions,
+    map: &[Vec<Option<Tile>>],
+    source: (usize, usize),
+    options: &SearchOptions,
+) -> Result<Vec<Frontier>, TomTomError> {
+    let graph = Graph::new(map, environmental_conditions, options);
+
+    search_frontiers(&graph, map, source).map(|(_, frontiers)| frontiers)
+}
+
+/// get_path_to_frontier returns the path from the source tile to the best ranked frontier.
+pub(crate) fn get_path_to_frontier(
+    environmental_conditions: &EnvironmentalConditions,
+    map: &[Vec<Option<Tile>>],
+    source: (usize, usize),
+    options: &SearchOptions,
+) -> Result<Path, TomTomError> {
+    let graph = Graph::new(map, environmental_conditions, options);
+    let (tree, frontiers) = search_frontiers(&graph, map, source)?;
+
+    frontiers
+        .first()
+        .and_then(|frontier| tree.path_to(&graph, frontier.coordinates))
+        .ok_or(TomTomError::Unreachable)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::{conditions, map};
+
+    #[test]
+    fn frontiers_are_ranked_by_distance_per_unknown_neighbour_then_by_distance() {
+        let map = map(&[". . . .", ". . . .", ". . . ?", "? . ? ?"]);
+
+        let frontiers =
+            get_frontiers(&conditions(), &map, (0, 0), &SearchOptions::default()).unwrap();
+
+        assert_eq!(
+            frontiers
+                .iter()
+                .map(|frontier| frontier.coordinates)
+                .collect::<Vec<_>>(),
+            vec![(2, 2), (2, 1), (2, 0), (3, 1), (1, 2), (1, 3)]
+        );
+        assert_eq!(
+            frontiers[0],
+            Frontier {
+                coordinates: (2, 2),
+                cost: 4,
+                unknown_neighbours: 3,
+            }
+        );
+    }
+
+    #[test]
+    fn unreachable_frontiers_are_skipped() {
+        let map = map(&[". # .", "# # .", ". . ?"]);
+
+        let frontiers =
+            get_frontiers(&conditions(), &map, (0, 0), &SearchOptions::default()).unwrap();
+        assert!(frontiers.is_empty());
+
+        assert!(matches!(
+            get_path_to_frontier(&conditions(), &map, (0, 0), &SearchOptions::default()),
+            Err(TomTomError::Unreachable)
+        ));
+    }
+
+    #[test]
+    fn the_source_tile_is_not_a_frontier() {
+        let map = map(&[". . .", "? . .", ". . ."]);
+
+        let frontiers =
+            get_frontiers(&conditions(), &map, (0, 0), &SearchOptions::default()).unwrap();
+        assert!(frontiers
+            .iter()
+            .all(|frontier| frontier.coordinates != (0, 0)));
+
+        let path =
+            get_path_to_frontier(&conditions(), &map, (0, 0), &SearchOptions::default()).unwrap();
+        assert_eq!(path.destination, (0, 1));
+        assert!(!path.actions.is_empty());
+
+        let map = crate::test_utils::map(&["? .", "# #"]);
+
+        assert!(matches!(
+            get_path_to_frontier(&conditions(), &map, (0, 1), &SearchOptions::default()),
+            Err(TomTomError::Unreachable)
+        ));
+    }
+}
@@ -1,17 +1,46 @@
-use std::collections::HashSet;
-
+use crate::alternatives::alternatives;
 use crate::dijkstra::dijkstra;
-use crate::path::{Action, Path};
-use crate::utils::{get_adjacent_tiles, get_specific_tiles};
+use crate::error::TomTomError;
+use crate::execution::{execute, ExecutionReport};
+use crate::filter::{get_matching_tiles, get_path_to_matching, TileFilter};
+use crate::frontier::{get_frontiers, get_path_to_frontier, Frontier};
+use crate::pareto::{pareto, ParetoFront, RiskModel};
+use crate::path::Path;
+use crate::reachability::{reachability, reachable_costs, Reachability};
+use crate::render::{render_ascii, render_svg};
+use crate::tour::{budgeted_tour, get_stops, tour, Budget, Tour};
+use crate::utils::get_targets;
+use crate::validation::{validate, Validation};
 use plain::{PlainContent, PlainTileType};
-use robotics_lib::interface::{go, robot_map, teleport, Tools};
+use robotics_lib::interface::{look_at_sky, robot_map, Tools};
 use robotics_lib::runner::Runnable;
-use robotics_lib::world::World;
+use robotics_lib::world::environmental_conditions::EnvironmentalConditions;
+use robotics_lib::world::{tile::Tile, World};
+use search::SearchOptions;
 
+mod alternatives;
+#[cfg(test)]
+mod bench;
+pub mod constraints;
+pub mod cost;
 mod dijkstra;
+pub mod error;
+pub mod execution;
+pub mod filter;
+pub mod forecast;
+pub mod frontier;
+pub mod pareto;
 pub mod path;
 pub mod plain;
+pub mod planner;
+pub mod reachability;
+mod render;
+pub mod search;
+#[cfg(test)]
+mod test_utils;
+pub mod tour;
 mod utils;
+pub mod validation;
 
 #[derive(Default)]
 pub struct TomTom {}
@@ -27,171 +56,649 @@ impl TomTom {
     /// - world: &World
     /// - adjacent: bool => if true the function will target the adjacent tiles to destination, if false it will target destination itself.
     /// - destination: (usize, usize) => destination tile of coordinates (row, col).
+    /// - options: &SearchOptions => options describing how the path is searched.
     ///
     /// # Return
-    /// - Result<Path, String> => Ok(path) returns the path, Err(e) represents a possible error described by String e.
+    /// - Result<Path, TomTomError> => Ok(path) returns the path, Err(e) represents a possible error described by TomTomError e.
     pub fn get_path_to_coordinates(
         robot: &impl Runnable,
         world: &World,
         adjacent: bool,
         destination: (usize, usize),
-    ) -> Result<Path, String> {
-        match robot_map(world) {
-            None => Err(String::from("Map not visible!")),
-            Some(map) => {
-                let source = (
-                    robot.get_coordinate().get_row(),
-                    robot.get_coordinate().get_col(),
-                );
-
-                let mut targets = HashSet::new();
+        options: &SearchOptions,
+    ) -> Result<Path, TomTomError> {
+        let view = robot_view(robot, world)?;
+        let targets = get_targets(&view.map, adjacent, [destination]);
 
-                if adjacent {
-                    targets.extend(get_adjacent_tiles(&map, destination));
-                } else {
-                    targets.insert(destination);
-                }
-
-                dijkstra(robot, world, &map, source, targets)
-            }
-        }
+        dijkstra(
+            &view.environmental_conditions,
+            &view.map,
+            view.source,
+            targets,
+            options,
+        )
     }
 
     /// get_path_to_tile returns the path having the smallest energy cost to reach the 'nearest' matched tile (or the 'nearest' adjacent tile),
     /// considering: go interface costs, tiles' walkability and elevation, environmental conditions and teleports.
-    /// Matched tiles are the tiles, discovered by the robot, that match the optional tile type and content.
+    /// Matched tiles are the tiles, discovered by the robot, that match the optional tile type and content, i.e. TileFilter::tile(tile_type, content).
     ///
     /// # Arguments
     /// - robot: &impl Runnable
     /// - world: &World
     /// - adjacent: bool => if true the function will target the adjacent tiles to the matched tiles, if false it will target the matched tiles themselves.
     /// - tile_type: Option<PlainTileType> => optional tile type to be matched.
-    /// - content: Option<PlainContent> => optional content to be matched.  
+    /// - content: Option<PlainContent> => optional content to be matched.
+    /// - options: &SearchOptions => options describing how the path is searched.
     ///
     /// # Return
-    /// - Result<Path, String> => Ok(path) returns the path, Err(e) represents a possible error described by String e.
+    /// - Result<Path, TomTomError> => Ok(path) returns the path, Err(e) represents a possible error described by TomTomError e.
     pub fn get_path_to_tile(
         robot: &impl Runnable,
         world: &World,
         adjacent: bool,
         tile_type: Option<PlainTileType>,
         content: Option<PlainContent>,
-    ) -> Result<Path, String> {
-        match robot_map(world) {
-            None => Err(String::from("Map not visible!")),
-            Some(map) => {
-                let source = (
-                    robot.get_coordinate().get_row(),
-                    robot.get_coordinate().get_col(),
-                );
-
-                let destinations = get_specific_tiles(&map, &tile_type, &content);
-                let mut targets = HashSet::new();
-
-                for destination in destinations {
-                    if adjacent {
-                        targets.extend(get_adjacent_tiles(&map, destination));
-                    } else {
-                        targets.insert(destination);
-                    }
-                }
-
-                dijkstra(robot, world, &map, source, targets)
-            }
-        }
+        options: &SearchOptions,
+    ) -> Result<Path, TomTomError> {
+        TomTom::get_path_to_matching(
+            robot,
+            world,
+            adjacent,
+            &TileFilter::tile(tile_type, content),
+            options,
+        )
+    }
+
+    /// get_path_to_matching returns the path having the smallest energy cost to reach the 'nearest' matched tile (or the 'nearest' adjacent tile),
+    /// considering: go interface costs, tiles' walkability and elevation, environmental conditions and teleports.
+    /// Matched tiles are the tiles, discovered by the robot, that match the filter.
+    ///
+    /// # Arguments
+    /// - robot: &impl Runnable
+    /// - world: &World
+    /// - adjacent: bool => if true the function will target the adjacent tiles to the matched tiles, if false it will target the matched tiles themselves.
+    /// - filter: &TileFilter => filter describing the tiles to be matched.
+    /// - options: &SearchOptions => options describing how the path is searched.
+    ///
+    /// # Return
+    /// - Result<Path, TomTomError> => Ok(path) returns the path, Err(e) represents a possible error described by TomTomError e.
+    pub fn get_path_to_matching(
+        robot: &impl Runnable,
+        world: &World,
+        adjacent: bool,
+        filter: &TileFilter,
+        options: &SearchOptions,
+    ) -> Result<Path, TomTomError> {
+        let view = robot_view(robot, world)?;
+
+        get_path_to_matching(
+            &view.environmental_conditions,
+            &view.map,
+            view.source,
+            adjacent,
+            filter,
+            options,
+        )
     }
 
     /// go_to_coordinates calls get_path_to_coordinates: if the result is Ok(path) and the robot has enough energy to complete the path, it moves
-    /// the robot to the path's destination tile.
+    /// the robot along the path, reporting the executed and remaining actions. If options.replan_limit is greater than 0, before each action
+    /// the path is searched again from the robot's coordinates when the action's cost or walkability changed in the robot map, stopping with NotEnoughEnergy if the robot can't afford the new path.
     ///
     /// # Arguments
     /// - robot: &impl Runnable
     /// - world: &World
     /// - adjacent: bool => if true the function will target the adjacent tiles to destination, if false it will target destination itself.
     /// - destination: (usize, usize) => destination tile of coordinates (row, col).
+    /// - options: &SearchOptions => options describing how the path is searched.
     ///
     /// # Return
-    /// - Result<Path, String> => Ok(path) returns the path, Err(e) represents a possible error described by String e.
+    /// - Result<ExecutionReport, TomTomError> => Ok(report) describes the execution of the path, possibly stopped by a failed action,
+    ///   Err(e) represents a possible error, preventing the execution, described by TomTomError e.
     pub fn go_to_coordinates(
         robot: &mut impl Runnable,
         world: &mut World,
         adjacent: bool,
         destination: (usize, usize),
-    ) -> Result<Path, String> {
-        match TomTom::get_path_to_coordinates(robot, world, adjacent, destination) {
-            Err(e) => Err(e),
-            Ok(path) => {
-                if !robot.get_energy().has_enough_energy(path.cost) {
-                    return Err(String::from("Not enough energy!"));
-                }
-
-                for action in path.actions.iter() {
-                    match action {
-                        Action::Go(d) => {
-                            if let Err(_) = go(robot, world, d.clone()) {
-                                return Err(String::from("Error while calling go interface!"));
-                            }
-                        }
-                        Action::Teleport((row, col)) => {
-                            if let Err(_) = teleport(robot, world, (*row, *col)) {
-                                return Err(String::from(
-                                    "Error while calling teleport interface!",
-                                ));
-                            }
-                        }
-                    }
-                }
-
-                Ok(path)
-            }
-        }
+        options: &SearchOptions,
+    ) -> Result<ExecutionReport, TomTomError> {
+        go_to(robot, world, options, |robot, world| {
+            TomTom::get_path_to_coordinates(robot, world, adjacent, destination, options)
+        })
     }
 
     /// go_to_tile calls get_path_to_tile: if the result is Ok(path) and the robot has enough energy to complete the path, it moves
-    /// the robot to the path's destination tile.
+    /// the robot along the path, reporting the executed and remaining actions. If options.replan_limit is greater than 0, before each action
+    /// the path is searched again from the robot's coordinates when the action's cost or walkability changed in the robot map, stopping with NotEnoughEnergy if the robot can't afford the new path.
     ///
     /// # Arguments
     /// - robot: &impl Runnable
     /// - world: &World
     /// - adjacent: bool => if true the function will target the adjacent tiles to the matched tiles, if false it will target the matched tiles themselves.
     /// - tile_type: Option<PlainTileType> => optional tile type to be matched.
-    /// - content: Option<PlainContent> => optional content to be matched.  
+    /// - content: Option<PlainContent> => optional content to be matched.
+    /// - options: &SearchOptions => options describing how the path is searched.
     ///
     /// # Return
-    /// - Result<Path, String> => Ok(path) returns the path, Err(e) represents a possible error described by String e.
+    /// - Result<ExecutionReport, TomTomError> => Ok(report) describes the execution of the path, possibly stopped by a failed action,
+    ///   Err(e) represents a possible error, preventing the execution, described by TomTomError e.
     pub fn go_to_tile(
         robot: &mut impl Runnable,
         world: &mut World,
         adjacent: bool,
         tile_type: Option<PlainTileType>,
         content: Option<PlainContent>,
-    ) -> Result<Path, String> {
-        match TomTom::get_path_to_tile(robot, world, adjacent, tile_type, content) {
-            Err(e) => Err(e),
-            Ok(path) => {
-                if !robot.get_energy().has_enough_energy(path.cost) {
-                    return Err(String::from("Not enough energy!"));
-                }
-
-                for action in path.actions.iter() {
-                    match action {
-                        Action::Go(d) => {
-                            if let Err(_) = go(robot, world, d.clone()) {
-                                return Err(String::from("Error while calling go interface!"));
-                            }
-                        }
-                        Action::Teleport((row, col)) => {
-                            if let Err(_) = teleport(robot, world, (*row, *col)) {
-                                return Err(String::from(
-                                    "Error while calling teleport interface!",
-                                ));
-                            }
-                        }
-                    }
-                }
-
-                Ok(path)
-            }
-        }
+        options: &SearchOptions,
+    ) -> Result<ExecutionReport, TomTomError> {
+        TomTom::go_to_matching(
+            robot,
+            world,
+            adjacent,
+            &TileFilter::tile(tile_type, content),
+            options,
+        )
+    }
+
+    /// go_to_matching calls get_path_to_matching: if the result is Ok(path) and the robot has enough energy to complete the path, it moves
+    /// the robot along the path, reporting the executed and remaining actions. If options.replan_limit is greater than 0, before each action
+    /// the path is searched again from the robot's coordinates when the action's cost or walkability changed in the robot map, stopping with NotEnoughEnergy if the robot can't afford the new path.
+    ///
+    /// # Arguments
+    /// - robot: &impl Runnable
+    /// - world: &World
+    /// - adjacent: bool => if true the function will target the adjacent tiles to the matched tiles, if false it will target the matched tiles themselves.
+    /// - filter: &TileFilter => filter describing the tiles to be matched.
+    /// - options: &SearchOptions => options describing how the path is searched.
+    ///
+    /// # Return
+    /// - Result<ExecutionReport, TomTomError> => Ok(report) describes the execution of the path, possibly stopped by a failed action,
+    ///   Err(e) represents a possible error, preventing the execution, described by TomTomError e.
+    pub fn go_to_matching(
+        robot: &mut impl Runnable,
+        world: &mut World,
+        adjacent: bool,
+        filter: &TileFilter,
+        options: &SearchOptions,
+    ) -> Result<ExecutionReport, TomTomError> {
+        go_to(robot, world, options, |robot, world| {
+            TomTom::get_path_to_matching(robot, world, adjacent, filter, options)
+        })
     }
+
+    /// get_tour_to_coordinates returns the tour visiting all the destination tiles at the given coordinates (or one of their adjacent tiles),
+    /// starting from the robot's tile and ordered to minimize the total energy cost. The order, and the adjacent tile reached for each one, is exact for up to 10 destinations,
+    /// otherwise it is computed with the nearest neighbour heuristic improved by 2-opt.
+    ///
+    /// # Arguments
+    /// - robot: &impl Runnable
+    /// - world: &World
+    /// - adjacent: bool => if true the function will target the adjacent tiles to each destination, if false it will target each destination itself.
+    /// - destinations: &[(usize, usize)] => destination tiles of coordinates (row, col).
+    /// - options: &SearchOptions => options describing how the paths are searched.
+    ///
+    /// # Return
+    /// - Result<Tour, TomTomError> => Ok(tour) returns the tour, Err(e) represents a possible error described by TomTomError e.
+    pub fn get_tour_to_coordinates(
+        robot: &impl Runnable,
+        world: &World,
+        adjacent: bool,
+        destinations: &[(usize, usize)],
+        options: &SearchOptions,
+    ) -> Result<Tour, TomTomError> {
+        let view = robot_view(robot, world)?;
+        let destinations: Vec<((usize, usize), usize)> = destinations
+            .iter()
+            .map(|destination| (*destination, 1))
+            .collect();
+        let stops = get_stops(&view.map, adjacent, &destinations);
+
+        tour(
+            &view.environmental_conditions,
+            &view.map,
+            view.source,
+            stops,
+            options,
+        )
+    }
+
+    /// get_tour_to_tiles returns the tour visiting all the matched tiles (or one of their adjacent tiles), starting from the robot's tile
+    /// and ordered to minimize the total energy cost. Matched tiles are the tiles, discovered by the robot, that match the optional tile type and content, i.e. TileFilter::tile(tile_type, content).
+    /// The order, and the adjacent tile reached for each one, is exact for up to 10 matched tiles, otherwise it is computed with the nearest neighbour heuristic improved by 2-opt.
+    ///
+    /// # Arguments
+    /// - robot: &impl Runnable
+    /// - world: &World
+    /// - adjacent: bool => if true the function will target the adjacent tiles to each matched tile, if false it will target each matched tile itself.
+    /// - tile_type: Option<PlainTileType> => optional tile type to be matched.
+    /// - content: Option<PlainContent> => optional content to be matched.
+    /// - options: &SearchOptions => options describing how the paths are searched.
+    ///
+    /// # Return
+    /// - Result<Tour, TomTomError> => Ok(tour) returns the tour, Err(e) represents a possible error described by TomTomError e.
+    pub fn get_tour_to_tiles(
+        robot: &impl Runnable,
+        world: &World,
+        adjacent: bool,
+        tile_type: Option<PlainTileType>,
+        content: Option<PlainContent>,
+        options: &SearchOptions,
+    ) -> Result<Tour, TomTomError> {
+        TomTom::get_tour_to_matching(
+            robot,
+            world,
+            adjacent,
+            &TileFilter::tile(tile_type, content),
+            options,
+        )
+    }
+
+    /// get_tour_to_matching returns the tour visiting all the matched tiles (or one of their adjacent tiles), starting from the robot's tile
+    /// and ordered to minimize the total energy cost. Matched tiles are the tiles, discovered by the robot, that match the filter.
+    /// The order, and the adjacent tile reached for each one, is exact for up to 10 matched tiles, otherwise it is computed with the nearest neighbour heuristic improved by 2-opt.
+    ///
+    /// # Arguments
+    /// - robot: &impl Runnable
+    /// - world: &World
+    /// - adjacent: bool => if true the function will target the adjacent tiles to each matched tile, if false it will target each matched tile itself.
+    /// - filter: &TileFilter => filter describing the tiles to be matched.
+    /// - options: &SearchOptions => options describing how the paths are searched.
+    ///
+    /// # Return
+    /// - Result<Tour, TomTomError> => Ok(tour) returns the tour, Err(e) represents a possible error described by TomTomError e.
+    pub fn get_tour_to_matching(
+        robot: &impl Runnable,
+        world: &World,
+        adjacent: bool,
+        filter: &TileFilter,
+        options: &SearchOptions,
+    ) -> Result<Tour, TomTomError> {
+        let view = robot_view(robot, world)?;
+        let destinations: Vec<((usize, usize), usize)> = get_matching_tiles(&view.map, filter)
+            .into_iter()
+            .map(|destination| (destination, 1))
+            .collect();
+        let stops = get_stops(&view.map, adjacent, &destinations);
+
+        tour(
+            &view.environmental_conditions,
+            &view.map,
+            view.source,
+            stops,
+            options,
+        )
+    }
+
+    /// get_budgeted_tour_to_coordinates returns the tour visiting the subset of the destination tiles at the given coordinates
+    /// (or one of their adjacent tiles) with the greatest total value whose energy cost fits the budget, starting from the robot's tile.
+    /// The subset, its order and the adjacent tiles reached are exact for up to 10 destinations, otherwise it is built by inserting the destinations with the greatest value
+    /// per energy cost and shortening the tour with 2-opt.
+    ///
+    /// # Arguments
+    /// - robot: &impl Runnable
+    /// - world: &World
+    /// - adjacent: bool => if true the function will target the adjacent tiles to each destination, if false it will target each destination itself.
+    /// - destinations: &[((usize, usize), usize)] => destination tiles of coordinates (row, col), each one with its value.
+    /// - budget: &Budget => energy the tour must fit, and whether it goes back to the robot's tile.
+    /// - options: &SearchOptions => options describing how the paths are searched.
+    ///
+    /// # Return
+    /// - Result<Tour, TomTomError> => Ok(tour) returns the tour, Err(e) represents a possible error described by TomTomError e.
+    pub fn get_budgeted_tour_to_coordinates(
+        robot: &impl Runnable,
+        world: &World,
+        adjacent: bool,
+        destinations: &[((usize, usize), usize)],
+        budget: &Budget,
+        options: &SearchOptions,
+    ) -> Result<Tour, TomTomError> {
+        let view = robot_view(robot, world)?;
+        let stops = get_stops(&view.map, adjacent, destinations);
+        let energy = budget
+            .energy
+            .unwrap_or(robot.get_energy().get_energy_level());
+
+        budgeted_tour(
+            &view.environmental_conditions,
+            &view.map,
+            view.source,
+            stops,
+            energy,
+            budget.return_to_source,
+            options,
+        )
+    }
+
+    /// get_budgeted_tour_to_tiles returns the tour visiting the subset of the matched tiles (or one of their adjacent tiles)
+    /// with the greatest total value whose energy cost fits the budget, starting from the robot's tile.
+    /// Matched tiles are the tiles, discovered by the robot, that match the filter.
+    /// The subset, its order and the adjacent tiles reached are exact for up to 10 matched tiles, otherwise it is built by inserting the tiles with the greatest value
+    /// per energy cost and shortening the tour with 2-opt.
+    ///
+    /// # Arguments
+    /// - robot: &impl Runnable
+    /// - world: &World
+    /// - adjacent: bool => if true the function will target the adjacent tiles to each matched tile, if false it will target each matched tile itself.
+    /// - filter: &TileFilter => filter describing the tiles to be matched.
+    /// - value: impl Fn((usize, usize), &Tile) -> usize => value of visiting the matched tile of coordinates (row, col), e.g. |_, _| 1 to maximize the count.
+    /// - budget: &Budget => energy the tour must fit, and whether it goes back to the robot's tile.
+    /// - options: &SearchOptions => options describing how the paths are searched.
+    ///
+    /// # Return
+    /// - Result<Tour, TomTomError> => Ok(tour) returns the tour, Err(e) represents a possible error described by TomTomError e.
+    pub fn get_budgeted_tour_to_tiles(
+        robot: &impl Runnable,
+        world: &World,
+        adjacent: bool,
+        filter: &TileFilter,
+        value: impl Fn((usize, usize), &Tile) -> usize,
+        budget: &Budget,
+        options: &SearchOptions,
+    ) -> Result<Tour, TomTomError> {
+        let view = robot_view(robot, world)?;
+        let destinations: Vec<((usize, usize), usize)> = get_matching_tiles(&view.map, filter)
+            .into_iter()
+            .filter_map(|(row, col)| {
+                view.map[row][col]
+                    .as_ref()
+                    .map(|tile| ((row, col), value((row, col), tile)))
+            })
+            .collect();
+        let stops = get_stops(&view.map, adjacent, &destinations);
+        let energy = budget
+            .energy
+            .unwrap_or(robot.get_energy().get_energy_level());
+
+        budgeted_tour(
+            &view.environmental_conditions,
+            &view.map,
+            view.source,
+            stops,
+            energy,
+            budget.return_to_source,
+            options,
+        )
+    }
+
+    /// get_frontiers returns the frontiers reachable by the robot, i.e. the discovered walkable tiles next to undiscovered tiles other than the robot's tile,
+    /// ranked from the most convenient to explore: smallest path cost per undiscovered tile revealed, then smallest path cost.
+    ///
+    /// # Arguments
+    /// - robot: &impl Runnable
+    /// - world: &World
+    /// - options: &SearchOptions => options describing how the paths are searched.
+    ///
+    /// # Return
+    /// - Result<Vec<Frontier>, TomTomError> => Ok(frontiers) returns the ranked frontiers (empty if none is reachable), Err(e) represents a possible error described by TomTomError e.
+    pub fn get_frontiers(
+        robot: &impl Runnable,
+        world: &World,
+        options: &SearchOptions,
+    ) -> Result<Vec<Frontier>, TomTomError> {
+        let view = robot_view(robot, world)?;
+
+        get_frontiers(
+            &view.environmental_conditions,
+            &view.map,
+            view.source,
+            options,
+        )
+    }
+
+    /// get_path_to_frontier returns the path to the best ranked frontier, see get_frontiers.
+    ///
+    /// # Arguments
+    /// - robot: &impl Runnable
+    /// - world: &World
+    /// - options: &SearchOptions => options describing how the path is searched.
+    ///
+    /// # Return
+    /// - Result<Path, TomTomError> => Ok(path) returns the path, Err(e) represents a possible error described by TomTomError e (Unreachable if no frontier is reachable).
+    pub fn get_path_to_frontier(
+        robot: &impl Runnable,
+        world: &World,
+        options: &SearchOptions,
+    ) -> Result<Path, TomTomError> {
+        let view = robot_view(robot, world)?;
+
+        get_path_to_frontier(
+            &view.environmental_conditions,
+            &view.map,
+            view.source,
+            options,
+        )
+    }
+
+    /// get_reachability searches the whole map known by the robot once, returning the energy cost of reaching every tile
+    /// from the robot's tile and the paths to them, e.g. to evaluate many candidate destinations.
+    ///
+    /// # Arguments
+    /// - robot: &impl Runnable
+    /// - world: &World
+    /// - max_cost: Option<usize> => if Some(max_cost), the tiles whose path costs more than max_cost are considered unreachable.
+    /// - options: &SearchOptions => options describing how the paths are searched.
+    ///
+    /// # Return
+    /// - Result<Reachability, TomTomError> => Ok(reachability) returns the reachable tiles, Err(e) represents a possible error described by TomTomError e.
+    pub fn get_reachability(
+        robot: &impl Runnable,
+        world: &World,
+        max_cost: Option<usize>,
+        options: &SearchOptions,
+    ) -> Result<Reachability, TomTomError> {
+        let view = robot_view(robot, world)?;
+
+        reachability(
+            view.environmental_conditions,
+            view.map,
+            view.source,
+            max_cost,
+            options,
+        )
+    }
+
+    /// get_alternative_paths_to_coordinates returns up to k loopless paths to reach the destination tile at the given coordinates
+    /// (or the 'nearest' adjacent tile), from the smallest energy cost, e.g. to avoid the best path when it crosses a risky zone.
+    ///
+    /// # Arguments
+    /// - robot: &impl Runnable
+    /// - world: &World
+    /// - adjacent: bool => if true the function will target the adjacent tiles to destination, if false it will target destination itself.
+    /// - destination: (usize, usize) => destination tile of coordinates (row, col).
+    /// - k: usize => maximum number of paths.
+    /// - min_diversity: Option<f64> => if Some(fraction), every path visits at least this fraction (from 0.0 to 1.0) of tiles not visited by each previous path.
+    /// - options: &SearchOptions => options describing how the paths are searched.
+    ///
+    /// # Return
+    /// - Result<Vec<Path>, TomTomError> => Ok(paths) returns the paths (the first one is the path returned by get_path_to_coordinates),
+    ///   Err(e) represents a possible error described by TomTomError e.
+    pub fn get_alternative_paths_to_coordinates(
+        robot: &impl Runnable,
+        world: &World,
+        adjacent: bool,
+        destination: (usize, usize),
+        k: usize,
+        min_diversity: Option<f64>,
+        options: &SearchOptions,
+    ) -> Result<Vec<Path>, TomTomError> {
+        let view = robot_view(robot, world)?;
+        let targets = get_targets(&view.map, adjacent, [destination]);
+
+        alternatives(
+            &view.environmental_conditions,
+            &view.map,
+            view.source,
+            targets,
+            k,
+            min_diversity,
+            options,
+        )
+    }
+
+    /// get_pareto_paths_to_coordinates returns the paths to reach the destination tile at the given coordinates (or the 'nearest' adjacent tile)
+    /// that trade off energy cost, number of actions and risk: every returned path is better than the others in at least one objective.
+    /// The exclusions of the constraints apply, the penalties don't.
+    ///
+    /// # Arguments
+    /// - robot: &impl Runnable
+    /// - world: &World
+    /// - adjacent: bool => if true the function will target the adjacent tiles to destination, if false it will target destination itself.
+    /// - destination: (usize, usize) => destination tile of coordinates (row, col).
+    /// - risk_model: &dyn RiskModel => danger of the tiles entered by the paths, e.g. &DefaultRiskModel.
+    /// - options: &SearchOptions => options describing how the paths are searched.
+    ///
+    /// # Return
+    /// - Result<ParetoFront, TomTomError> => Ok(front) returns the paths ordered by energy cost (empty if the destination is unreachable)
+    ///   and whether the front has been truncated, Err(e) represents a possible error described by TomTomError e.
+    pub fn get_pareto_paths_to_coordinates(
+        robot: &impl Runnable,
+        world: &World,
+        adjacent: bool,
+        destination: (usize, usize),
+        risk_model: &dyn RiskModel,
+        options: &SearchOptions,
+    ) -> Result<ParetoFront, TomTomError> {
+        let view = robot_view(robot, world)?;
+        let targets = get_targets(&view.map, adjacent, [destination]);
+
+        pareto(
+            &view.environmental_conditions,
+            &view.map,
+            view.source,
+            &targets,
+            risk_model,
+            options,
+        )
+    }
+
+    /// validate simulates the actions of the path against the current robot map before performing them, since the map may have changed
+    /// after the path was searched: every action must stay within the map bounds, enter discovered walkable tiles, teleport between
+    /// active teleports and be affordable with the robot's energy. Actions are costed with the current environmental conditions.
+    ///
+    /// # Arguments
+    /// - robot: &impl Runnable
+    /// - world: &World
+    /// - path: &Path => path to be validated, starting from the robot's tile.
+    /// - options: &SearchOptions => options whose cost model recomputes the cost of the path.
+    ///
+    /// # Return
+    /// - Result<Validation, TomTomError> => Ok(validation) returns the first invalid action and the recomputed cost, Err(e) represents a possible error described by TomTomError e.
+    pub fn validate(
+        robot: &impl Runnable,
+        world: &World,
+        path: &Path,
+        options: &SearchOptions,
+    ) -> Result<Validation, TomTomError> {
+        let view = robot_view(robot, world)?;
+
+        Ok(validate(
+            view.source,
+            robot.get_energy().get_energy_level(),
+            &view.environmental_conditions,
+            &view.map,
+            path,
+            options,
+        ))
+    }
+
+    /// render_map draws the map known by the robot as text, e.g. to print it in terminal logs: every tile is drawn with the glyph
+    /// of its type (or of its content), undiscovered tiles with ?, and the optional path is drawn over the map.
+    ///
+    /// # Arguments
+    /// - robot: &impl Runnable
+    /// - world: &World
+    /// - path: Option<&Path> => if Some(path), every tile of the path is drawn with the direction of the action leaving it (^, v, <, >) or T
+    ///   for a teleport, the source tile with S and the destination tile with X.
+    /// - contents: bool => if true the tiles with a content are drawn with the glyph of the content instead of the glyph of their type.
+    ///
+    /// # Return
+    /// - Result<String, TomTomError> => Ok(map) returns the rows of the map separated by newlines, the robot's tile drawn with @,
+    ///   Err(e) represents a possible error described by TomTomError e.
+    pub fn render_map(
+        robot: &impl Runnable,
+        world: &World,
+        path: Option<&Path>,
+        contents: bool,
+    ) -> Result<String, TomTomError> {
+        let view = robot_view(robot, world)?;
+
+        Ok(render_ascii(&view.map, view.source, path, contents))
+    }
+
+    /// render_svg draws the map known by the robot as an SVG document, e.g. for reports: every tile is filled with the colour of its type
+    /// and darkened by its elevation, the optional cost heatmap and the paths are drawn over the map.
+    ///
+    /// # Arguments
+    /// - robot: &impl Runnable
+    /// - world: &World
+    /// - paths: &[Path] => paths drawn as lines of distinct colours, dashed for teleports, with a circle on the source tile and a square on the destination tile.
+    /// - heatmap: bool => if true the energy cost of reaching every tile from the robot's tile is drawn from blue (cheapest) to red (most expensive).
+    /// - options: &SearchOptions => options describing how the heatmap costs are searched.
+    ///
+    /// # Return
+    /// - Result<String, TomTomError> => Ok(svg) returns the SVG document, the robot's tile marked by a white circle,
+    ///   Err(e) represents a possible error described by TomTomError e.
+    pub fn render_svg(
+        robot: &impl Runnable,
+        world: &World,
+        paths: &[Path],
+        heatmap: bool,
+        options: &SearchOptions,
+    ) -> Result<String, TomTomError> {
+        let view = robot_view(robot, world)?;
+        let costs = match heatmap {
+            false => None,
+            true => Some(reachable_costs(
+                &view.environmental_conditions,
+                &view.map,
+                view.source,
+                None,
+                options,
+            )?),
+        };
+
+        Ok(render_svg(&view.map, view.source, paths, costs.as_deref()))
+    }
+}
+
+/// RobotView stores what the entry points search: the map known by the robot, the robot's coordinates (row, col)
+/// and the current environmental conditions.
+struct RobotView {
+    map: Vec<Vec<Option<Tile>>>,
+    source: (usize, usize),
+    environmental_conditions: EnvironmentalConditions,
+}
+
+/// robot_view returns the view of the robot, Err(TomTomError::MapNotVisible) if the robot map is not visible.
+fn robot_view(robot: &impl Runnable, world: &World) -> Result<RobotView, TomTomError> {
+    let map = robot_map(world).ok_or(TomTomError::MapNotVisible)?;
+    let source = (
+        robot.get_coordinate().get_row(),
+        robot.get_coordinate().get_col(),
+    );
+
+    Ok(RobotView {
+        map,
+        source,
+        environmental_conditions: look_at_sky(world),
+    })
+}
+
+/// go_to plans the path and, if the robot has enough energy to complete it, executes it, planning it again when needed.
+fn go_to<R: Runnable>(
+    robot: &mut R,
+    world: &mut World,
+    options: &SearchOptions,
+    plan: impl Fn(&R, &World) -> Result<Path, TomTomError>,
+) -> Result<ExecutionReport, TomTomError> {
+    let path = plan(robot, world)?;
+
+    if !robot.get_energy().has_enough_energy(path.cost) {
+        return Err(TomTomError::NotEnoughEnergy {
+            required: path.cost,
+            available: robot.get_energy().get_energy_level(),
+        });
+    }
+
+    Ok(execute(robot, world, path, options, plan))
 }
@@ -0,0 +1,430 @@
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashSet, VecDeque};
+
+use robotics_lib::world::environmental_conditions::EnvironmentalConditions;
+use robotics_lib::world::tile::{Content, Tile, TileType};
+
+use crate::dijkstra::Graph;
+use crate::error::TomTomError;
+use crate::path::{Action, Path, StepCost};
+use crate::search::SearchOptions;
+
+/// MAX_LABELS_PER_TILE bounds the number of non-dominated partial paths kept for every tile, so the front may miss some paths on large maps:
+/// the front is then reported as truncated.
+const MAX_LABELS_PER_TILE: usize = 64;
+
+/// RiskModel describes the danger of the tiles crossed by a path.
+pub trait RiskModel {
+    /// risk returns the danger of entering the tile.
+    ///
+    /// # Arguments
+    /// - map: &[Vec<Option<Tile>>] => map known by the robot.
+    /// - coordinates: (usize, usize) => entered tile of coordinates (row, col).
+    ///
+    /// # Return
+    /// - usize => danger score of the tile, 0 meaning no danger.
+    fn risk(&self, map: &[Vec<Option<Tile>>], coordinates: (usize, usize)) -> usize;
+}
+
+/// DefaultRiskModel scores 1 for every tile with Fire content or of type Lava among the entered tile and its 8 surrounding tiles.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct DefaultRiskModel;
+
+impl RiskModel for DefaultRiskModel {
+    fn risk(&self, map: &[Vec<Option<Tile>>], coordinates: (usize, usize)) -> usize {
+        let (row, col) = coordinates;
+        let size = map.len();
+
+        let rows = row.saturating_sub(1)..=(row + 1).min(size - 1);
+        let cols = col.saturating_sub(1)..=(col + 1).min(size - 1);
+
+        map[rows]
+            .iter()
+            .flat_map(|tiles| tiles[cols.clone()].iter())
+            .flatten()
+            .filter(|tile| {
+                matches!(tile.content, Content::Fire) || tile.tile_type == TileType::Lava
+            })
+            .count()
+    }
+}
+
+/// ParetoPath describes a path of the Pareto front: no other path of the front is better in every objective.
+///
+/// # Fields:
+/// - path: Path => the path.
+/// - energy: usize => energy cost of the path.
+/// - steps: usize => number of actions of the path.
+/// - risk: usize => sum of the danger scores of the tiles entered by the path.
+#[derive(Debug, Clone)]
+pub struct ParetoPath {
+    pub path: Path,
+    pub energy: usize,
+    pub steps: usize,
+    pub risk: usize,
+}
+
+/// ParetoFront describes the paths of the Pareto front.
+///
+/// # Fields:
+/// - paths: Vec<ParetoPath> => paths of the front ordered by energy cost, empty if the targets are unreachable.
+/// - truncated: bool => true if some partial paths have been dropped because a tile reached the limit of 64 partial paths,
+///   so the front may miss some paths.
+#[derive(Debug, Default, Clone)]
+pub struct ParetoFront {
+    pub paths: Vec<ParetoPath>,
+    pub truncated: bool,
+}
+
+type Objectives = (usize, usize, usize);
+
+/// Label is a partial path reaching a tile, linked to the label of the previous tile.
+struct Label {
+    node: (usize, usize),
+    objectives: Objectives,
+    unknown_steps: usize,
+    parent: Option<(usize, Action, StepCost)>,
+    dominated: bool,
+}
+
+fn dominates(a: Objectives, b: Objectives) -> bool {
+    a.0 <= b.0 && a.1 <= b.1 && a.2 <= b.2
+}
+
+/// pareto returns the front of the paths from the source tile to the targets whose energy, steps and risk are not all worse
+/// than another path's, ordered by energy. The exclusions of the constraints apply, the penalties don't.
+pub(crate) fn pareto(
+    environmental_conditions: &EnvironmentalConditions,
+    map: &[Vec<Option<Tile>>],
+    source: (usize, usize),
+    targets: &HashSet<(usize, usize)>,
+    risk_model: &dyn RiskModel,
+    options: &SearchOptions,
+) -> Result<ParetoFront, TomTomError> {
+    let size = map.len();
+
+    if source.0 >= size || source.1 >= size {
+        return Err(TomTomError::SourceOutOfBounds);
+    }
+
+    if targets.is_empty() {
+        return Err(TomTomError::NoTargets);
+    }
+
+    let graph = Graph::new(map, environmental_conditions, options);
+
+    let mut labels = vec![Label {
+        node: source,
+        objectives: (0, 0, 0),
+        unknown_steps: 0,
+        parent: None,
+        dominated: false,
+    }];
+    let mut tiles_labels: Vec<Vec<Vec<usize>>> = vec![vec![Vec::new(); size]; size];
+    tiles_labels[source.0][source.1].push(0);
+
+    let mut front: Vec<usize> = Vec::new();
+    let mut truncated = false;
+
+    let mut heap = BinaryHeap::new();
+    heap.push(Reverse(((0, 0, 0), 0)));
+
+    let mut edges = Vec::new();
+
+    while let Some(Reverse((objectives, index))) = heap.pop() {
+        if labels[index].dominated
+            || front
+                .iter()
+                .any(|front_index| dominates(labels[*front_index].objectives, objectives))
+        {
+            continue;
+        }
+
+        let node = labels[index].node;
+
+        if targets.contains(&node) {
+            front.push(index);
+            continue;
+        }
+
+        let (energy, steps, risk) = objectives;
+
+        graph.edges(node, steps, &mut edges);
+
+        for edge in edges.drain(..) {
+            let next = (
+                energy + edge.cost,
+                steps + 1,
+                risk + risk_model.risk(map, edge.node),
+            );
+
+            let next_labels = &mut tiles_labels[edge.node.0][edge.node.1];
+
+            if next_labels
+                .iter()
+                .any(|label_index| dominates(labels[*label_index].objectives, next))
+                || front
+                    .iter()
+                    .any(|front_index| dominates(labels[*front_index].objectives, next))
+            {
+                continue;
+            }
+
+            next_labels.retain(|label_index| {
+                let dominated = dominates(next, labels[*label_index].objectives);
+                labels[*label_index].dominated |= dominated;
+                !dominated
+            });
+
+            if next_labels.len() >= MAX_LABELS_PER_TILE {
+                truncated = true;
+                continue;
+            }
+
+            next_labels.push(labels.len());
+            heap.push(Reverse((next, labels.len())));
+            labels.push(Label {
+                node: edge.node,
+                objectives: next,
+                unknown_steps: labels[index].unknown_steps + usize::from(edge.unknown),
+                parent: Some((
+                    index,
+                    edge.action.clone(),
+                    graph.step_cost(node, &edge, steps),
+                )),
+                dominated: false,
+            });
+        }
+    }
+
+    let paths = front
+        .into_iter()
+        .map(|index| {
+            let (energy, steps, risk) = labels[index].objectives;
+
+            let mut actions = VecDeque::new();
+            let mut breakdown = VecDeque::new();
+            let mut label = &labels[index];
+
+            while let Some((parent, action, step_cost)) = &label.parent {
+                actions.push_front(action.clone());
+                breakdown.push_front(step_cost.clone());
+                label = &labels[*parent];
+            }
+
+            let mut path = Path::new(source, labels[index].node, energy);
+            path.actions = actions;
+            path.breakdown = breakdown.into();
+            path.unknown_steps = labels[index].unknown_steps;
+
+            ParetoPath {
+                path,
+                energy,
+                steps,
+                risk,
+            }
+        })
+        .collect();
+
+    Ok(ParetoFront { paths, truncated })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::{conditions, map, tile, Rng};
+
+    /// loopless_objectives pushes to ret the objectives of every loopless path from the last visited tile to the destination.
+    fn loopless_objectives(
+        graph: &Graph,
+        map: &[Vec<Option<Tile>>],
+        visited: &mut Vec<(usize, usize)>,
+        destination: (usize, usize),
+        objectives: Objectives,
+        ret: &mut Vec<Objectives>,
+    ) {
+        let node = visited[visited.len() - 1];
+
+        if node == destination {
+            ret.push(objectives);
+            return;
+        }
+
+        let (energy, steps, risk) = objectives;
+        let mut edges = Vec::new();
+        graph.edges(node, steps, &mut edges);
+
+        for edge in edges {
+            if !visited.contains(&edge.node) {
+                let next = (
+                    energy + edge.cost,
+                    steps + 1,
+                    risk + DefaultRiskModel.risk(map, edge.node),
+                );
+
+                visited.push(edge.node);
+                loopless_objectives(graph, map, visited, destination, next, ret);
+                visited.pop();
+            }
+        }
+    }
+
+    /// expected_front returns the objectives of the loopless paths not dominated by another path, ordered and without repetitions.
+    fn expected_front(
+        map: &[Vec<Option<Tile>>],
+        source: (usize, usize),
+        destination: (usize, usize),
+    ) -> Vec<Objectives> {
+        let environmental_conditions = conditions();
+        let options = SearchOptions::default();
+        let graph = Graph::new(map, &environmental_conditions, &options);
+
+        let mut objectives = Vec::new();
+        loopless_objectives(
+            &graph,
+            map,
+            &mut vec![source],
+            destination,
+            (0, 0, 0),
+            &mut objectives,
+        );
+        objectives.sort();
+        objectives.dedup();
+
+        objectives
+            .iter()
+            .copied()
+            .filter(|a| !objectives.iter().any(|b| b != a && dominates(*b, *a)))
+            .collect()
+    }
+
+    fn front(
+        map: &[Vec<Option<Tile>>],
+        source: (usize, usize),
+        destination: (usize, usize),
+    ) -> ParetoFront {
+        pareto(
+            &conditions(),
+            map,
+            source,
+            &HashSet::from([destination]),
+            &DefaultRiskModel,
+            &SearchOptions::default(),
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn front_trades_off_energy_steps_and_risk() {
+        let map = map(&[
+            ". . . . .",
+            ". % % % .",
+            ". . . . .",
+            ": : : : :",
+            ". . . . .",
+        ]);
+        let (source, destination) = ((0, 0), (0, 4));
+
+        let front = front(&map, source, destination);
+        let objectives: Vec<Objectives> = front
+            .paths
+            .iter()
+            .map(|path| (path.energy, path.steps, path.risk))
+            .collect();
+
+        assert!(!front.truncated);
+        assert_eq!(objectives, expected_front(&map, source, destination));
+
+        // the straight path along the lava is the cheapest, a detour away from it is safer
+        assert_eq!(front.paths[0].steps, 4);
+        assert!(front
+            .paths
+            .iter()
+            .any(|path| path.risk < front.paths[0].risk));
+
+        for (index, a) in front.paths.iter().enumerate() {
+            assert_eq!(a.path.cost, a.energy);
+            assert_eq!(a.path.actions.len(), a.steps);
+            assert_eq!(a.path.destination, destination);
+
+            for b in front.paths[index + 1..].iter() {
+                assert!(!dominates(
+                    (a.energy, a.steps, a.risk),
+                    (b.energy, b.steps, b.risk)
+                ));
+                assert!(!dominates(
+                    (b.energy, b.steps, b.risk),
+                    (a.energy, a.steps, a.risk)
+                ));
+            }
+        }
+    }
+
+    #[test]
+    fn front_matches_the_loopless_paths_on_random_maps() {
+        const GLYPHS: [char; 5] = ['.', ':', '+', '%', '#'];
+
+        let mut rng = Rng::new(17);
+
+        for _ in 0..100 {
+            let rows: Vec<String> = (0..4)
+                .map(|_| (0..4).map(|_| GLYPHS[rng.next(GLYPHS.len())]).collect())
+                .collect();
+            let rows: Vec<&str> = rows.iter().map(String::as_str).collect();
+            let mut map = map(&rows);
+            map[0][0] = Some(tile(TileType::Grass));
+            map[3][3] = Some(tile(TileType::Grass));
+
+            let front = front(&map, (0, 0), (3, 3));
+            let objectives: Vec<Objectives> = front
+                .paths
+                .iter()
+                .map(|path| (path.energy, path.steps, path.risk))
+                .collect();
+
+            assert!(!front.truncated);
+            assert_eq!(objectives, expected_front(&map, (0, 0), (3, 3)));
+        }
+    }
+
+    /// Lanes scores the tiles of the second row with a risk varying along the columns, the other tiles are safe.
+    struct Lanes;
+
+    impl RiskModel for Lanes {
+        fn risk(&self, _map: &[Vec<Option<Tile>>], coordinates: (usize, usize)) -> usize {
+            match coordinates.0 {
+                1 => 1 + (coordinates.1 * 7) % 5,
+                _ => 0,
+            }
+        }
+    }
+
+    #[test]
+    fn front_is_truncated_when_a_tile_has_too_many_partial_paths() {
+        const TILE_TYPES: [TileType; 3] = [TileType::Sand, TileType::Hill, TileType::Snow];
+
+        for (size, truncated) in [(10, false), (80, true)] {
+            // two lanes along the first rows: every column trades the energy of the first lane for the risk of the second one,
+            // so every tile is reached by more non-dominated partial paths than the previous one
+            let mut map = vec![vec![Some(tile(TileType::Wall)); size]; size];
+
+            for col in 0..size {
+                map[0][col] = Some(tile(TILE_TYPES[col % TILE_TYPES.len()]));
+                map[1][col] = Some(tile(TileType::Grass));
+            }
+
+            let front = pareto(
+                &conditions(),
+                &map,
+                (0, 0),
+                &HashSet::from([(0, size - 1)]),
+                &Lanes,
+                &SearchOptions::default(),
+            )
+            .unwrap();
+
+            assert_eq!(front.truncated, truncated);
+            assert!(!front.paths.is_empty());
+        }
+    }
+}
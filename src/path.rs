@@ -1,6 +1,18 @@
 use std::collections::VecDeque;
+use std::fmt::{Display, Formatter};
+use std::iter::Peekable;
+use std::str::{CharIndices, FromStr};
 
-use robotics_lib::interface::Direction;
+use robotics_lib::interface::{look_at_sky, robot_map, Direction};
+use robotics_lib::world::environmental_conditions::EnvironmentalConditions;
+use robotics_lib::world::{tile::Tile, World};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::dijkstra::Graph;
+use crate::error::TomTomError;
+use crate::search::SearchOptions;
+use crate::utils::get_action_destination;
 
 /// Action enumerates the possible actions of a path.
 ///
@@ -8,11 +20,84 @@ use robotics_lib::interface::Direction;
 /// - Go(Direction) => go to the parameter direction.
 /// - Teleport((usize, usize)) => teleport to the tile of the parameter coordinates (row, col).
 #[derive(Debug, Clone)]
+#[cfg_attr(
+    feature = "serde",
+    derive(Serialize, Deserialize),
+    serde(from = "ActionRepr", into = "ActionRepr")
+)]
 pub enum Action {
     Go(Direction),
     Teleport((usize, usize)),
 }
 
+/// ActionRepr is the serialized form of Action, since Direction is not serializable: {"go": "up"} or {"teleport": [row, col]}.
+#[cfg(feature = "serde")]
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+enum ActionRepr {
+    Go(DirectionRepr),
+    Teleport((usize, usize)),
+}
+
+#[cfg(feature = "serde")]
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+enum DirectionRepr {
+    Up,
+    Down,
+    Left,
+    Right,
+}
+
+#[cfg(feature = "serde")]
+impl From<Action> for ActionRepr {
+    fn from(action: Action) -> Self {
+        match action {
+            Action::Go(Direction::Up) => ActionRepr::Go(DirectionRepr::Up),
+            Action::Go(Direction::Down) => ActionRepr::Go(DirectionRepr::Down),
+            Action::Go(Direction::Left) => ActionRepr::Go(DirectionRepr::Left),
+            Action::Go(Direction::Right) => ActionRepr::Go(DirectionRepr::Right),
+            Action::Teleport(destination) => ActionRepr::Teleport(destination),
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+impl From<ActionRepr> for Action {
+    fn from(action: ActionRepr) -> Self {
+        match action {
+            ActionRepr::Go(DirectionRepr::Up) => Action::Go(Direction::Up),
+            ActionRepr::Go(DirectionRepr::Down) => Action::Go(Direction::Down),
+            ActionRepr::Go(DirectionRepr::Left) => Action::Go(Direction::Left),
+            ActionRepr::Go(DirectionRepr::Right) => Action::Go(Direction::Right),
+            ActionRepr::Teleport(destination) => Action::Teleport(destination),
+        }
+    }
+}
+
+/// StepCost describes the energy cost of an action of a path, split as the robotics_lib interfaces compute it.
+///
+/// # Fields:
+/// - coordinates: (usize, usize) => coordinates (row, col) of the robot after the action.
+/// - base_cost: usize => cost of the destination tile type (0 for a teleport).
+/// - environment_cost: usize => base cost adjusted by the environmental conditions of the action (0 for a teleport).
+/// - elevation_cost: usize => square of the elevation gain (0 for a teleport).
+/// - teleport_cost: usize => cost of the teleport (0 for a go action).
+/// - cost: usize => cost of the action according to the cost model used to search the path, equal to environment_cost + elevation_cost + teleport_cost
+///   unless a custom cost model is used or the action involves undiscovered tiles (then the other costs are 0).
+/// - unknown: bool => true if the action is from or to an undiscovered tile, whose cost is assumed (see SearchOptions::unknown_cost).
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct StepCost {
+    pub coordinates: (usize, usize),
+    pub base_cost: usize,
+    pub environment_cost: usize,
+    pub elevation_cost: usize,
+    pub teleport_cost: usize,
+    pub cost: usize,
+    pub unknown: bool,
+}
+
 /// Path describes the path from a source tile to a destination tile, with specific cost and actions.
 ///
 /// # Fields:
@@ -20,12 +105,18 @@ pub enum Action {
 /// - destination: (usize, usize) => destination tile of coordinates (row, col).
 /// - actions: Vec<Action> => actions to be done to move from the source tile to the destination tile.
 /// - cost: usize => energy cost of the path.
+/// - unknown_steps: usize => number of actions from or to undiscovered tiles, whose cost is assumed (see SearchOptions::unknown_cost):
+///   a path with unknown steps is tentative and may change as the tiles are discovered.
+/// - breakdown: Vec<StepCost> => cost of every action, in the same order as actions.
 #[derive(Debug, Default, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct Path {
     pub source: (usize, usize),
     pub destination: (usize, usize),
     pub actions: VecDeque<Action>,
     pub cost: usize,
+    pub unknown_steps: usize,
+    pub breakdown: Vec<StepCost>,
 }
 
 impl Path {
@@ -35,6 +126,622 @@ impl Path {
             destination,
             actions: VecDeque::new(),
             cost,
+            unknown_steps: 0,
+            breakdown: Vec::new(),
+        }
+    }
+
+    /// len returns the number of actions of the path.
+    pub fn len(&self) -> usize {
+        self.actions.len()
+    }
+
+    /// is_empty returns true if the path has no actions, i.e. the source tile is the destination tile.
+    pub fn is_empty(&self) -> bool {
+        self.actions.is_empty()
+    }
+
+    /// coordinates returns an iterator over the coordinates (row, col) of the tiles visited by the path,
+    /// from the source tile to the destination tile.
+    pub fn coordinates(&self) -> impl Iterator<Item = (usize, usize)> + '_ {
+        let mut position = Some(self.source);
+
+        std::iter::once(self.source).chain(self.actions.iter().map_while(move |action| {
+            position = get_action_destination(usize::MAX, position?, action);
+            position
+        }))
+    }
+
+    /// contains returns true if the path visits the tile of the given coordinates (row, col).
+    pub fn contains(&self, coordinates: (usize, usize)) -> bool {
+        self.coordinates().any(|visited| visited == coordinates)
+    }
+
+    /// bounding_box returns the smallest box containing the tiles visited by the path.
+    ///
+    /// # Return
+    /// - ((usize, usize), (usize, usize)) => coordinates (row, col) of the top left corner and of the bottom right corner.
+    pub fn bounding_box(&self) -> ((usize, usize), (usize, usize)) {
+        self.coordinates().fold(
+            (self.source, self.source),
+            |((min_row, min_col), (max_row, max_col)), (row, col)| {
+                (
+                    (min_row.min(row), min_col.min(col)),
+                    (max_row.max(row), max_col.max(col)),
+                )
+            },
+        )
+    }
+
+    /// split_at splits the path at the tile reached after the given number of actions.
+    /// The cost and the unknown steps are split with the breakdown.
+    ///
+    /// # Arguments
+    /// - index: usize => number of actions of the first part.
+    ///
+    /// # Return
+    /// - Option<(Path, Path)> => Some((first, second)) returns the parts, None means index is greater than the number of actions.
+    pub fn split_at(&self, index: usize) -> Option<(Path, Path)> {
+        if index > self.len() {
+            return None;
+        }
+
+        let middle = self.coordinates().nth(index)?;
+
+        let mut first = Path::new(self.source, middle, 0);
+        first.actions = self.actions.iter().take(index).cloned().collect();
+        first.breakdown = self.breakdown.iter().take(index).cloned().collect();
+        first.cost = first.breakdown.iter().map(|step| step.cost).sum();
+        first.unknown_steps = first.breakdown.iter().filter(|step| step.unknown).count();
+
+        let mut second = Path::new(
+            middle,
+            self.destination,
+            self.cost.saturating_sub(first.cost),
+        );
+        second.actions = self.actions.iter().skip(index).cloned().collect();
+        second.breakdown = self.breakdown.iter().skip(index).cloned().collect();
+        second.unknown_steps = self.unknown_steps.saturating_sub(first.unknown_steps);
+
+        Some((first, second))
+    }
+
+    /// concat returns the path performing the actions of this path, then the actions of the other path.
+    ///
+    /// # Arguments
+    /// - other: &Path => path whose source tile is the destination tile of this path.
+    ///
+    /// # Return
+    /// - Option<Path> => Some(path) returns the path, None means the other path doesn't start from the destination tile of this path.
+    pub fn concat(&self, other: &Path) -> Option<Path> {
+        if self.destination != other.source {
+            return None;
+        }
+
+        let mut path = Path::new(self.source, other.destination, self.cost + other.cost);
+        path.actions = self
+            .actions
+            .iter()
+            .chain(other.actions.iter())
+            .cloned()
+            .collect();
+        path.breakdown = self
+            .breakdown
+            .iter()
+            .chain(other.breakdown.iter())
+            .cloned()
+            .collect();
+        path.unknown_steps = self.unknown_steps + other.unknown_steps;
+
+        Some(path)
+    }
+
+    /// parse_from parses the actions encoded as by Display, ignoring whitespace, and follows them from the source tile
+    /// to compute the destination tile. Only the actions are encoded, so the parsed path has cost 0 and an empty breakdown
+    /// until it is costed against the map, e.g. with validate.
+    ///
+    /// # Arguments
+    /// - source: (usize, usize) => source tile of coordinates (row, col).
+    /// - s: &str => encoded actions, e.g. RRDDL T(12,4) UU.
+    ///
+    /// # Return
+    /// - Result<Path, TomTomError> => Ok(path) returns the path, Err(e) represents a possible error described by TomTomError e
+    ///   (InvalidEncoding with the byte index of the invalid character, OutOfBounds with the tile a go action leaves the map from).
+    pub fn parse_from(source: (usize, usize), s: &str) -> Result<Path, TomTomError> {
+        let mut chars = s.char_indices().peekable();
+        let actions = parse_actions(&mut chars, s.len())?;
+
+        Path::follow(source, actions)
+    }
+
+    /// follow returns the path performing the actions from the source tile, without costs.
+    fn follow(source: (usize, usize), actions: VecDeque<Action>) -> Result<Path, TomTomError> {
+        let mut path = Path::new(source, source, 0);
+
+        for action in actions {
+            path.destination = get_action_destination(usize::MAX, path.destination, &action)
+                .ok_or(TomTomError::OutOfBounds(path.destination))?;
+            path.actions.push_back(action);
         }
+
+        Ok(path)
+    }
+
+    /// reverse returns the path from the destination tile to the source tile, visiting the same tiles backwards:
+    /// go actions take the opposite direction and teleports go back to the previous tile, since active teleports are bidirectional.
+    /// Elevation makes the cost of the reversed path different, so its actions are costed again on the robot map.
+    ///
+    /// # Arguments
+    /// - world: &World
+    /// - options: &SearchOptions => options describing how the actions are costed.
+    ///
+    /// # Return
+    /// - Result<Path, TomTomError> => Ok(path) returns the reversed path, Err(e) represents a possible error described by TomTomError e
+    ///   (Unreachable if an action can't be performed backwards on the robot map).
+    pub fn reverse(&self, world: &World, options: &SearchOptions) -> Result<Path, TomTomError> {
+        match robot_map(world) {
+            None => Err(TomTomError::MapNotVisible),
+            Some(map) => self.reverse_on(&look_at_sky(world), &map, options),
+        }
+    }
+
+    /// reverse_on returns the reversed path costed on the map with the environmental conditions.
+    pub(crate) fn reverse_on(
+        &self,
+        environmental_conditions: &EnvironmentalConditions,
+        map: &[Vec<Option<Tile>>],
+        options: &SearchOptions,
+    ) -> Result<Path, TomTomError> {
+        let coordinates: Vec<(usize, usize)> = self.coordinates().collect();
+
+        let actions: Vec<Action> = self
+            .actions
+            .iter()
+            .zip(coordinates.iter())
+            .rev()
+            .map(|(action, previous)| match action {
+                Action::Go(Direction::Up) => Action::Go(Direction::Down),
+                Action::Go(Direction::Down) => Action::Go(Direction::Up),
+                Action::Go(Direction::Left) => Action::Go(Direction::Right),
+                Action::Go(Direction::Right) => Action::Go(Direction::Left),
+                Action::Teleport(_) => Action::Teleport(*previous),
+            })
+            .collect();
+
+        Graph::new(map, environmental_conditions, options)
+            .path(self.destination, &actions, 0)
+            .ok_or(TomTomError::Unreachable)
+    }
+}
+
+impl Display for Action {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Action::Go(Direction::Up) => write!(f, "U"),
+            Action::Go(Direction::Down) => write!(f, "D"),
+            Action::Go(Direction::Left) => write!(f, "L"),
+            Action::Go(Direction::Right) => write!(f, "R"),
+            Action::Teleport((row, col)) => write!(f, "T({},{})", row, col),
+        }
+    }
+}
+
+/// from_str parses a single action encoded as by Display: U, D, L, R or T(row,col).
+impl FromStr for Action {
+    type Err = TomTomError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut chars = s.char_indices().peekable();
+
+        let action = parse_action(&mut chars, s.len())?;
+
+        match chars.next() {
+            None => Ok(action),
+            Some((index, _)) => Err(TomTomError::InvalidEncoding(index)),
+        }
+    }
+}
+
+/// Display encodes the actions of the path: go actions as U, D, L and R, teleports as T(row,col) separated by spaces, e.g. RRDDL T(12,4) UU.
+/// The alternate format {:#} prefixes the source tile as (row,col)@, e.g. (3,2)@RRDDL T(12,4) UU, so that parsing it restores the source
+/// and destination tiles.
+impl Display for Path {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let mut previous_teleport = false;
+
+        if f.alternate() {
+            write!(f, "({},{})@", self.source.0, self.source.1)?;
+        }
+
+        for (index, action) in self.actions.iter().enumerate() {
+            let teleport = matches!(action, Action::Teleport(_));
+
+            if index > 0 && (teleport || previous_teleport) {
+                write!(f, " ")?;
+            }
+
+            write!(f, "{}", action)?;
+            previous_teleport = teleport;
+        }
+
+        Ok(())
+    }
+}
+
+/// from_str parses the actions encoded as by Display, ignoring whitespace, optionally prefixed by the source tile as (row,col)@,
+/// and follows them from the source tile as by parse_from to compute the destination tile. Without the prefix the source tile is (0, 0),
+/// so a go action leaving the map from the tiles followed from there returns OutOfBounds. The parsed path has cost 0 and an empty breakdown.
+impl FromStr for Path {
+    type Err = TomTomError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut chars = s.char_indices().peekable();
+
+        while chars.next_if(|(_, c)| c.is_whitespace()).is_some() {}
+
+        let mut source = (0, 0);
+
+        if chars.next_if(|(_, c)| *c == '(').is_some() {
+            let row = parse_coordinate(&mut chars, s.len())?;
+            expect(&mut chars, ',', s.len())?;
+            let col = parse_coordinate(&mut chars, s.len())?;
+            expect(&mut chars, ')', s.len())?;
+            expect(&mut chars, '@', s.len())?;
+
+            source = (row, col);
+        }
+
+        Path::follow(source, parse_actions(&mut chars, s.len())?)
+    }
+}
+
+fn parse_actions(
+    chars: &mut Peekable<CharIndices>,
+    end: usize,
+) -> Result<VecDeque<Action>, TomTomError> {
+    let mut actions = VecDeque::new();
+
+    loop {
+        while chars.next_if(|(_, c)| c.is_whitespace()).is_some() {}
+
+        if chars.peek().is_none() {
+            return Ok(actions);
+        }
+
+        actions.push_back(parse_action(chars, end)?);
+    }
+}
+
+fn parse_action(chars: &mut Peekable<CharIndices>, end: usize) -> Result<Action, TomTomError> {
+    match chars.next() {
+        None => Err(TomTomError::InvalidEncoding(end)),
+        Some((_, 'U')) => Ok(Action::Go(Direction::Up)),
+        Some((_, 'D')) => Ok(Action::Go(Direction::Down)),
+        Some((_, 'L')) => Ok(Action::Go(Direction::Left)),
+        Some((_, 'R')) => Ok(Action::Go(Direction::Right)),
+        Some((_, 'T')) => {
+            expect(chars, '(', end)?;
+            let row = parse_coordinate(chars, end)?;
+            expect(chars, ',', end)?;
+            let col = parse_coordinate(chars, end)?;
+            expect(chars, ')', end)?;
+
+            Ok(Action::Teleport((row, col)))
+        }
+        Some((index, _)) => Err(TomTomError::InvalidEncoding(index)),
+    }
+}
+
+fn expect(
+    chars: &mut Peekable<CharIndices>,
+    expected: char,
+    end: usize,
+) -> Result<(), TomTomError> {
+    while chars.next_if(|(_, c)| c.is_whitespace()).is_some() {}
+
+    match chars.next() {
+        Some((_, c)) if c == expected => Ok(()),
+        Some((index, _)) => Err(TomTomError::InvalidEncoding(index)),
+        None => Err(TomTomError::InvalidEncoding(end)),
+    }
+}
+
+fn parse_coordinate(chars: &mut Peekable<CharIndices>, end: usize) -> Result<usize, TomTomError> {
+    while chars.next_if(|(_, c)| c.is_whitespace()).is_some() {}
+
+    let start = chars.peek().map_or(end, |(index, _)| *index);
+    let mut coordinate: Option<usize> = None;
+
+    while let Some((index, digit)) = chars.next_if(|(_, c)| c.is_ascii_digit()) {
+        let digit = (digit as u8 - b'0') as usize;
+
+        coordinate = Some(
+            coordinate
+                .unwrap_or(0)
+                .checked_mul(10)
+                .and_then(|coordinate| coordinate.checked_add(digit))
+                .ok_or(TomTomError::InvalidEncoding(index))?,
+        );
+    }
+
+    coordinate.ok_or(TomTomError::InvalidEncoding(start))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use robotics_lib::world::tile::TileType;
+
+    use super::*;
+    use crate::dijkstra::dijkstra;
+    use crate::test_utils::{conditions, map, tile};
+
+    fn search(
+        map: &[Vec<Option<Tile>>],
+        source: (usize, usize),
+        destination: (usize, usize),
+        options: &SearchOptions,
+    ) -> Path {
+        dijkstra(
+            &conditions(),
+            map,
+            source,
+            HashSet::from([destination]),
+            options,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn split_at_splits_the_unknown_steps() {
+        let map = map(&[
+            ". ? ? . .",
+            ". # # # .",
+            ". . . . .",
+            ". . . . .",
+            ". . . . .",
+        ]);
+        let options = SearchOptions {
+            unknown_cost: Some(1),
+            ..SearchOptions::default()
+        };
+
+        let path = search(&map, (0, 0), (0, 4), &options);
+        assert_eq!(path.unknown_steps, 3);
+
+        for index in 0..=path.len() {
+            let (first, second) = path.split_at(index).unwrap();
+
+            assert_eq!(
+                first.unknown_steps,
+                path.breakdown[..index]
+                    .iter()
+                    .filter(|step| step.unknown)
+                    .count()
+            );
+            assert_eq!(
+                first.unknown_steps + second.unknown_steps,
+                path.unknown_steps
+            );
+            assert_eq!(first.cost + second.cost, path.cost);
+
+            let joined = first.concat(&second).unwrap();
+            assert_eq!(joined.unknown_steps, path.unknown_steps);
+            assert_eq!(joined.cost, path.cost);
+            assert_eq!(joined.breakdown, path.breakdown);
+            assert_eq!(joined.to_string(), path.to_string());
+        }
+
+        assert!(path.split_at(path.len() + 1).is_none());
+    }
+
+    #[test]
+    fn reverse_costs_the_actions_backwards() {
+        let mut map = map(&[
+            ". . . .", //
+            "# # # .", "# # # .", "# # # .",
+        ]);
+        // the first row climbs up to the last column
+        for (col, tile) in map[0].iter_mut().enumerate() {
+            tile.as_mut().unwrap().elevation = col;
+        }
+        for row in map[1..].iter_mut() {
+            row[3].as_mut().unwrap().elevation = 3;
+        }
+
+        let options = SearchOptions::default();
+        let path = search(&map, (0, 0), (3, 3), &options);
+        let reversed = path.reverse_on(&conditions(), &map, &options).unwrap();
+
+        assert_eq!((reversed.source, reversed.destination), ((3, 3), (0, 0)));
+        assert_eq!(reversed.to_string(), "UUULLL");
+        assert_eq!(
+            reversed
+                .breakdown
+                .iter()
+                .map(|step| step.cost)
+                .sum::<usize>(),
+            reversed.cost
+        );
+        // going downhill costs no elevation
+        assert!(reversed.cost < path.cost);
+
+        let back = reversed.reverse_on(&conditions(), &map, &options).unwrap();
+        assert_eq!(back.to_string(), path.to_string());
+        assert_eq!(back.cost, path.cost);
+
+        map[0][0] = Some(tile(TileType::Wall));
+        assert!(matches!(
+            path.reverse_on(&conditions(), &map, &options),
+            Err(TomTomError::Unreachable)
+        ));
+    }
+
+    #[test]
+    fn reverse_teleports_back_to_the_previous_tile() {
+        let map = map(&[
+            ". # # # .",
+            "O # # # O",
+            "# # # # #",
+            "# # # # #",
+            "# # # # #",
+        ]);
+
+        let options = SearchOptions::default();
+        let path = search(&map, (0, 0), (0, 4), &options);
+        assert_eq!(path.to_string(), "D T(1,4) U");
+
+        let reversed = path.reverse_on(&conditions(), &map, &options).unwrap();
+        assert_eq!(reversed.to_string(), "D T(1,0) U");
+        assert_eq!(reversed.cost, path.cost);
+        assert_eq!(reversed.breakdown.len(), reversed.len());
+    }
+
+    #[test]
+    #[cfg(feature = "serde")]
+    fn action_serializes_as_a_lowercase_map() {
+        let actions = [
+            (Action::Go(Direction::Up), r#"{"go":"up"}"#),
+            (Action::Go(Direction::Down), r#"{"go":"down"}"#),
+            (Action::Go(Direction::Left), r#"{"go":"left"}"#),
+            (Action::Go(Direction::Right), r#"{"go":"right"}"#),
+            (Action::Teleport((12, 4)), r#"{"teleport":[12,4]}"#),
+        ];
+
+        for (action, json) in actions {
+            assert_eq!(serde_json::to_string(&action).unwrap(), json);
+
+            let parsed: Action = serde_json::from_str(json).unwrap();
+            assert_eq!(parsed.to_string(), action.to_string());
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "serde")]
+    fn path_round_trips_through_json() {
+        let map = map(&[
+            ". # # # .",
+            "O # # # O",
+            "# # # # #",
+            "# # # # #",
+            "# # # # #",
+        ]);
+        let path = search(&map, (0, 0), (0, 4), &SearchOptions::default());
+
+        let json = serde_json::to_string(&path).unwrap();
+        assert!(json.starts_with(
+            r#"{"source":[0,0],"destination":[0,4],"actions":[{"go":"down"},{"teleport":[1,4]},{"go":"up"}],"#
+        ));
+
+        let parsed: Path = serde_json::from_str(&json).unwrap();
+        assert_eq!(
+            (parsed.source, parsed.destination),
+            (path.source, path.destination)
+        );
+        assert_eq!(parsed.to_string(), path.to_string());
+        assert_eq!(parsed.cost, path.cost);
+        assert_eq!(parsed.unknown_steps, path.unknown_steps);
+        assert_eq!(parsed.breakdown, path.breakdown);
+    }
+
+    #[test]
+    #[cfg(feature = "serde")]
+    fn step_cost_round_trips_through_json() {
+        let step_cost = StepCost {
+            coordinates: (3, 2),
+            base_cost: 3,
+            environment_cost: 4,
+            elevation_cost: 1,
+            teleport_cost: 0,
+            cost: 5,
+            unknown: false,
+        };
+
+        let json = serde_json::to_string(&step_cost).unwrap();
+        assert_eq!(
+            json,
+            r#"{"coordinates":[3,2],"base_cost":3,"environment_cost":4,"elevation_cost":1,"teleport_cost":0,"cost":5,"unknown":false}"#
+        );
+        assert_eq!(serde_json::from_str::<StepCost>(&json).unwrap(), step_cost);
+    }
+
+    #[test]
+    fn parses_the_actions_from_the_source() {
+        let path = Path::parse_from((3, 2), "RRDDL T(12,4) UU").unwrap();
+        assert_eq!((path.source, path.destination), ((3, 2), (10, 4)));
+        assert_eq!(path.to_string(), "RRDDL T(12,4) UU");
+
+        let path: Path = " (3, 2) @ RRDDL T(12,4) UU ".parse().unwrap();
+        assert_eq!((path.source, path.destination), ((3, 2), (10, 4)));
+        assert_eq!(format!("{path:#}"), "(3,2)@RRDDL T(12,4) UU");
+
+        let path: Path = "RRDDL T(12,4) UU".parse().unwrap();
+        assert_eq!((path.source, path.destination), ((0, 0), (10, 4)));
+        assert_eq!(path.len(), 8);
+
+        let parsed: Path = path.to_string().parse().unwrap();
+        assert_eq!(
+            (parsed.source, parsed.destination),
+            (path.source, path.destination)
+        );
+        assert_eq!(parsed.to_string(), path.to_string());
+        assert_eq!(parsed.coordinates().last(), Some(parsed.destination));
+
+        let path: Path = "(5,5)@".parse().unwrap();
+        assert_eq!((path.source, path.destination), ((5, 5), (5, 5)));
+        assert!(path.is_empty());
+    }
+
+    #[test]
+    fn alternate_format_round_trips_a_searched_path() {
+        let map = map(&[
+            ". # # # .",
+            "O # # # O",
+            "# # # # #",
+            "# # # # #",
+            "# # # # #",
+        ]);
+        let path = search(&map, (0, 0), (0, 4), &SearchOptions::default());
+
+        let parsed: Path = format!("{path:#}").parse().unwrap();
+        assert_eq!(
+            (parsed.source, parsed.destination),
+            (path.source, path.destination)
+        );
+        assert_eq!(parsed.to_string(), path.to_string());
+    }
+
+    #[test]
+    fn reports_where_the_encoding_is_invalid() {
+        let invalid = |s: &str| match s.parse::<Path>() {
+            Err(TomTomError::InvalidEncoding(index)) => index,
+            other => panic!("{s}: {other:?}"),
+        };
+
+        assert_eq!(invalid("RX"), 1);
+        assert_eq!(invalid("R T(1,2"), 7);
+        assert_eq!(invalid("T(,2)"), 2);
+        assert_eq!(invalid("(3,2)@RX"), 7);
+        assert_eq!(invalid("(3,2)RR"), 5);
+        assert_eq!(invalid("(3,2"), 4);
+        assert_eq!(invalid("(99999999999999999999999,2)@R"), 20);
+
+        assert!(matches!(
+            "(0,1)@LL".parse::<Path>(),
+            Err(TomTomError::OutOfBounds((0, 0)))
+        ));
+        assert!(matches!(
+            "RUU".parse::<Path>(),
+            Err(TomTomError::OutOfBounds((0, 1)))
+        ));
+        assert!(matches!(
+            Path::parse_from((0, 0), "U"),
+            Err(TomTomError::OutOfBounds((0, 0)))
+        ));
+        assert!(matches!(
+            Path::parse_from((0, 0), "(0,0)@R"),
+            Err(TomTomError::InvalidEncoding(0))
+        ));
     }
 }
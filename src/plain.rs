@@ -1,6 +1,9 @@
 use robotics_lib::world::tile::{Content, TileType};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum PlainTileType {
     DeepWater,
     ShallowWater,
@@ -33,7 +36,26 @@ impl PlainTileType {
     }
 }
 
+impl From<&TileType> for PlainTileType {
+    fn from(tile_type: &TileType) -> Self {
+        match tile_type {
+            TileType::DeepWater => PlainTileType::DeepWater,
+            TileType::ShallowWater => PlainTileType::ShallowWater,
+            TileType::Sand => PlainTileType::Sand,
+            TileType::Grass => PlainTileType::Grass,
+            TileType::Street => PlainTileType::Street,
+            TileType::Hill => PlainTileType::Hill,
+            TileType::Mountain => PlainTileType::Mountain,
+            TileType::Snow => PlainTileType::Snow,
+            TileType::Lava => PlainTileType::Lava,
+            TileType::Teleport(_) => PlainTileType::Teleport,
+            TileType::Wall => PlainTileType::Wall,
+        }
+    }
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum PlainContent {
     Rock,
     Tree,
@@ -75,3 +97,55 @@ impl PlainContent {
         }
     }
 }
+
+impl From<&Content> for PlainContent {
+    fn from(content: &Content) -> Self {
+        match content {
+            Content::Rock(_) => PlainContent::Rock,
+            Content::Tree(_) => PlainContent::Tree,
+            Content::Garbage(_) => PlainContent::Garbage,
+            Content::Fire => PlainContent::Fire,
+            Content::Coin(_) => PlainContent::Coin,
+            Content::Bin(_) => PlainContent::Bin,
+            Content::Crate(_) => PlainContent::Crate,
+            Content::Bank(_) => PlainContent::Bank,
+            Content::Water(_) => PlainContent::Water,
+            Content::Market(_) => PlainContent::Market,
+            Content::Fish(_) => PlainContent::Fish,
+            Content::Building => PlainContent::Building,
+            Content::Bush(_) => PlainContent::Bush,
+            Content::JollyBlock(_) => PlainContent::JollyBlock,
+            Content::Scarecrow => PlainContent::Scarecrow,
+            Content::None => PlainContent::None,
+        }
+    }
+}
+
+#[cfg(all(test, feature = "serde"))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn plain_types_round_trip_through_json() {
+        for (tile_type, json) in [
+            (PlainTileType::Grass, r#""Grass""#),
+            (PlainTileType::Teleport, r#""Teleport""#),
+            (PlainTileType::DeepWater, r#""DeepWater""#),
+        ] {
+            assert_eq!(serde_json::to_string(&tile_type).unwrap(), json);
+            assert_eq!(
+                serde_json::from_str::<PlainTileType>(json).unwrap(),
+                tile_type
+            );
+        }
+
+        for (content, json) in [
+            (PlainContent::Coin, r#""Coin""#),
+            (PlainContent::JollyBlock, r#""JollyBlock""#),
+            (PlainContent::None, r#""None""#),
+        ] {
+            assert_eq!(serde_json::to_string(&content).unwrap(), json);
+            assert_eq!(serde_json::from_str::<PlainContent>(json).unwrap(), content);
+        }
+    }
+}
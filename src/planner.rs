@@ -0,0 +1,512 @@
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashSet};
+
+use robotics_lib::interface::{look_at_sky, robot_map};
+use robotics_lib::runner::Runnable;
+use robotics_lib::world::environmental_conditions::EnvironmentalConditions;
+use robotics_lib::world::{tile::Tile, World};
+
+use crate::dijkstra::{Edge, Graph, Heuristic};
+use crate::error::TomTomError;
+use crate::path::{Action, Path};
+use crate::search::{SearchAlgorithm, SearchOptions};
+
+type Key = (usize, usize);
+
+/// Queue is the priority queue of the inconsistent tiles, removed tiles are discarded lazily.
+struct Queue {
+    heap: BinaryHeap<Reverse<(Key, (usize, usize))>>,
+    keys: Vec<Vec<Option<Key>>>,
+}
+
+impl Queue {
+    fn new(size: usize) -> Queue {
+        Queue {
+            heap: BinaryHeap::new(),
+            keys: vec![vec![None; size]; size],
+        }
+    }
+
+    fn insert(&mut self, node: (usize, usize), key: Key) {
+        self.keys[node.0][node.1] = Some(key);
+        self.heap.push(Reverse((key, node)));
+    }
+
+    fn remove(&mut self, node: (usize, usize)) {
+        self.keys[node.0][node.1] = None;
+    }
+
+    fn top(&mut self) -> Option<(Key, (usize, usize))> {
+        while let Some(Reverse((key, node))) = self.heap.peek() {
+            if self.keys[node.0][node.1] == Some(*key) {
+                return Some((*key, *node));
+            }
+
+            self.heap.pop();
+        }
+
+        None
+    }
+}
+
+/// Search is the state of D* Lite: distances are computed backwards, from every tile to the destination tile.
+/// Actions costing 0 would let tiles in a cycle support each other's outdated distance, so every action weighs its distance
+/// multiplied by the number of tiles plus 1: ties are broken by the number of actions, without changing the optimal distance.
+struct Search {
+    source: (usize, usize),
+    destination: (usize, usize),
+    scale: usize,
+    km: usize,
+    g: Vec<Vec<usize>>,
+    rhs: Vec<Vec<usize>>,
+    queue: Queue,
+}
+
+impl Search {
+    fn new(size: usize, source: (usize, usize), destination: (usize, usize)) -> Search {
+        let mut search = Search {
+            source,
+            destination,
+            scale: size * size + 1,
+            km: 0,
+            g: vec![vec![usize::MAX; size]; size],
+            rhs: vec![vec![usize::MAX; size]; size],
+            queue: Queue::new(size),
+        };
+
+        search.rhs[destination.0][destination.1] = 0;
+        search
+            .queue
+            .insert(destination, search.key(None, destination));
+
+        search
+    }
+
+    fn weight(&self, edge: &Edge) -> usize {
+        edge.distance * self.scale + 1
+    }
+
+    fn estimate(&self, heuristic: Option<&Heuristic>, node: (usize, usize)) -> usize {
+        heuristic.map_or(0, |heuristic| heuristic.estimate(node) * self.scale)
+    }
+
+    fn key(&self, heuristic: Option<&Heuristic>, node: (usize, usize)) -> Key {
+        let distance = self.g[node.0][node.1].min(self.rhs[node.0][node.1]);
+
+        if distance == usize::MAX {
+            return (usize::MAX, usize::MAX);
+        }
+
+        let estimate = self.estimate(heuristic, node);
+
+        (
+            distance.saturating_add(estimate).saturating_add(self.km),
+            distance,
+        )
+    }
+
+    fn update(&mut self, graph: &Graph, heuristic: Option<&Heuristic>, node: (usize, usize)) {
+        if node != self.destination {
+            let mut edges = Vec::new();
+            graph.edges(node, 0, &mut edges);
+
+            self.rhs[node.0][node.1] = edges
+                .iter()
+                .filter_map(|edge| match self.g[edge.node.0][edge.node.1] {
+                    usize::MAX => None,
+                    g => Some(g + self.weight(edge)),
+                })
+                .min()
+                .unwrap_or(usize::MAX);
+        }
+
+        self.queue.remove(node);
+
+        if self.g[node.0][node.1] != self.rhs[node.0][node.1] {
+            self.queue.insert(node, self.key(heuristic, node));
+        }
+    }
+
+    fn compute(&mut self, graph: &Graph, heuristic: Option<&Heuristic>) {
+        while let Some((key, node)) = self.queue.top() {
+            let (source_row, source_col) = self.source;
+
+            if key > self.key(heuristic, self.source)
+                && self.rhs[source_row][source_col] == self.g[source_row][source_col]
+            {
+                break;
+            }
+
+            let (row, col) = node;
+            let new_key = self.key(heuristic, node);
+
+            if key < new_key {
+                self.queue.insert(node, new_key);
+            } else if self.g[row][col] > self.rhs[row][col] {
+                self.g[row][col] = self.rhs[row][col];
+                self.queue.remove(node);
+
+                for neighbour in graph.neighbours(node) {
+                    self.update(graph, heuristic, neighbour);
+                }
+            } else {
+                self.g[row][col] = usize::MAX;
+
+                for neighbour in graph.neighbours(node) {
+                    self.update(graph, heuristic, neighbour);
+                }
+
+                self.update(graph, heuristic, node);
+            }
+        }
+    }
+}
+
+/// Planner searches the path to a fixed destination tile with D* Lite: instead of searching the whole path again, every update
+/// of the robot map repairs the previous search where tiles have been discovered or changed, which makes it convenient
+/// to call every tick while the robot moves towards the destination.
+/// A change of the environmental conditions or of the active teleports changes many actions at once, so the planner searches again from scratch.
+/// Every action is costed with the current environmental conditions, the forecast of the search options is ignored.
+pub struct Planner {
+    destination: (usize, usize),
+    options: SearchOptions,
+    map: Vec<Vec<Option<Tile>>>,
+    environmental_conditions: Option<EnvironmentalConditions>,
+    teleports: Vec<(usize, usize)>,
+    search: Option<Search>,
+}
+
+impl Planner {
+    /// new returns a planner towards the destination tile, the first path is searched by update_map.
+    ///
+    /// # Arguments
+    /// - destination: (usize, usize) => destination tile of coordinates (row, col).
+    /// - options: SearchOptions => options describing how the path is searched, except the forecast.
+    ///
+    /// # Return
+    /// - Planner => the planner.
+    pub fn new(destination: (usize, usize), mut options: SearchOptions) -> Planner {
+        options.forecast = None;
+
+        Planner {
+            destination,
+            options,
+            map: Vec::new(),
+            environmental_conditions: None,
+            teleports: Vec::new(),
+            search: None,
+        }
+    }
+
+    /// update_map reads the robot's coordinates, the robot map and the environmental conditions, then repairs the search
+    /// for the tiles that have been discovered or changed since the previous update.
+    ///
+    /// # Arguments
+    /// - robot: &impl Runnable
+    /// - world: &World
+    ///
+    /// # Return
+    /// - Result<(), TomTomError> => Ok(()) means the search is up to date, Err(e) represents a possible error described by TomTomError e.
+    pub fn update_map(&mut self, robot: &impl Runnable, world: &World) -> Result<(), TomTomError> {
+        let map = match robot_map(world) {
+            None => return Err(TomTomError::MapNotVisible),
+            Some(map) => map,
+        };
+
+        let source = (
+            robot.get_coordinate().get_row(),
+            robot.get_coordinate().get_col(),
+        );
+
+        self.update(source, map, look_at_sky(world))
+    }
+
+    /// update repairs the search from the source tile for the tiles of the map that have been discovered or changed since the previous update.
+    fn update(
+        &mut self,
+        source: (usize, usize),
+        map: Vec<Vec<Option<Tile>>>,
+        environmental_conditions: EnvironmentalConditions,
+    ) -> Result<(), TomTomError> {
+        let size = map.len();
+
+        if source.0 >= size || source.1 >= size {
+            return Err(TomTomError::SourceOutOfBounds);
+        }
+
+        if self.destination.0 >= size || self.destination.1 >= size {
+            return Err(TomTomError::OutOfBounds(self.destination));
+        }
+
+        let same_conditions = match &self.environmental_conditions {
+            None => false,
+            Some(previous) => {
+                previous.get_weather_condition() == environmental_conditions.get_weather_condition()
+                    && previous.get_time_of_day() == environmental_conditions.get_time_of_day()
+            }
+        };
+
+        let mut changed = Vec::new();
+
+        if self.map.len() == size {
+            for (row, (tiles, previous_tiles)) in map.iter().zip(self.map.iter()).enumerate() {
+                for (col, (tile, previous_tile)) in tiles.iter().zip(previous_tiles).enumerate() {
+                    let same_tile = match (tile, previous_tile) {
+                        (None, None) => true,
+                        (Some(tile), Some(previous_tile)) => {
+                            tile.tile_type == previous_tile.tile_type
+                                && tile.content == previous_tile.content
+                                && tile.elevation == previous_tile.elevation
+                        }
+                        _ => false,
+                    };
+
+                    if !same_tile {
+                        changed.push((row, col));
+                    }
+                }
+            }
+        }
+
+        let restart = !same_conditions || self.map.len() != size;
+
+        self.map = map;
+        let environmental_conditions = self
+            .environmental_conditions
+            .insert(environmental_conditions);
+
+        let graph = Graph::new(&self.map, environmental_conditions, &self.options);
+        let restart = restart || graph.teleports() != self.teleports.as_slice();
+        self.teleports = graph.teleports().to_vec();
+
+        let heuristic = match self.options.algorithm {
+            SearchAlgorithm::Dijkstra => None,
+            SearchAlgorithm::AStar => Some(Heuristic::new(
+                &HashSet::from([source]),
+                graph.teleports(),
+                graph.min_go_cost(),
+            )),
+        };
+
+        let search = match (&mut self.search, restart) {
+            (Some(search), false) => {
+                search.km += search.estimate(heuristic.as_ref(), search.source);
+                search.source = source;
+
+                for tile in changed {
+                    search.update(&graph, heuristic.as_ref(), tile);
+
+                    for neighbour in graph.neighbours(tile) {
+                        search.update(&graph, heuristic.as_ref(), neighbour);
+                    }
+                }
+
+                search
+            }
+            _ => self
+                .search
+                .insert(Search::new(size, source, self.destination)),
+        };
+
+        search.compute(&graph, heuristic.as_ref());
+
+        Ok(())
+    }
+
+    /// next_action returns the first action of the path from the robot's coordinates of the last update_map.
+    ///
+    /// # Return
+    /// - Result<Option<Action>, TomTomError> => Ok(Some(action)) returns the action, Ok(None) means the robot is on the destination tile,
+    ///   Err(e) represents a possible error described by TomTomError e.
+    pub fn next_action(&self) -> Result<Option<Action>, TomTomError> {
+        self.path().map(|mut path| path.actions.pop_front())
+    }
+
+    /// path returns the path from the robot's coordinates of the last update_map to the destination tile.
+    ///
+    /// # Return
+    /// - Result<Path, TomTomError> => Ok(path) returns the path, Err(e) represents a possible error described by TomTomError e
+    ///   (MapNotVisible if update_map has never succeeded).
+    pub fn path(&self) -> Result<Path, TomTomError> {
+        let search = match &self.search {
+            None => return Err(TomTomError::MapNotVisible),
+            Some(search) => search,
+        };
+        let environmental_conditions = match &self.environmental_conditions {
+            None => return Err(TomTomError::MapNotVisible),
+            Some(environmental_conditions) => environmental_conditions,
+        };
+
+        let graph = Graph::new(&self.map, environmental_conditions, &self.options);
+
+        let mut path = Path::new(search.source, self.destination, 0);
+        let mut node = search.source;
+        let mut edges = Vec::new();
+
+        while node != self.destination {
+            if search.g[node.0][node.1] == usize::MAX {
+                return Err(TomTomError::Unreachable);
+            }
+
+            graph.edges(node, 0, &mut edges);
+
+            let edge = edges
+                .drain(..)
+                .filter(|edge| search.g[edge.node.0][edge.node.1] != usize::MAX)
+                .min_by_key(|edge| search.g[edge.node.0][edge.node.1] + search.weight(edge))
+                .ok_or(TomTomError::Unreachable)?;
+
+            if search.g[edge.node.0][edge.node.1] >= search.g[node.0][node.1] {
+                return Err(TomTomError::Unreachable);
+            }
+
+            path.cost += edge.cost;
+            path.unknown_steps += usize::from(edge.unknown);
+            path.breakdown
+                .push(graph.step_cost(node, &edge, path.actions.len()));
+            path.actions.push_back(edge.action);
+            node = edge.node;
+        }
+
+        Ok(path)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dijkstra::dijkstra;
+    use crate::test_utils::{conditions, map, random_map, tile, Rng};
+    use crate::utils::get_next_coords;
+    use robotics_lib::world::tile::TileType;
+
+    /// assert_same_cost asserts the planner's path costs as much as the path searched from scratch on the same map.
+    fn assert_same_cost(planner: &Planner, source: (usize, usize), map: &[Vec<Option<Tile>>]) {
+        let expected = dijkstra(
+            &conditions(),
+            map,
+            source,
+            HashSet::from([planner.destination]),
+            &planner.options,
+        );
+
+        match (planner.path(), expected) {
+            (Ok(path), Ok(expected)) => {
+                assert_eq!(path.cost, expected.cost);
+                assert_eq!(path.source, source);
+                assert_eq!(path.destination, planner.destination);
+                assert_eq!(
+                    path.breakdown.iter().map(|step| step.cost).sum::<usize>(),
+                    path.cost
+                );
+            }
+            (Err(TomTomError::Unreachable), Err(_)) => {}
+            (path, expected) => panic!("planner: {path:?}, search: {expected:?}"),
+        }
+    }
+
+    #[test]
+    fn repairs_the_search_after_every_map_change() {
+        for algorithm in [SearchAlgorithm::Dijkstra, SearchAlgorithm::AStar] {
+            let options = SearchOptions {
+                algorithm,
+                ..SearchOptions::default()
+            };
+            let mut planner = Planner::new((6, 6), options);
+            let mut source = (0, 0);
+            let mut map = map(&[
+                ". . . . . . .",
+                ". . . . . . .",
+                ". . . . . . .",
+                "# # # ? # # .",
+                ". . . . . . .",
+                ". . . . . . .",
+                ". . . . . . .",
+            ]);
+
+            planner.update(source, map.clone(), conditions()).unwrap();
+            assert_same_cost(&planner, source, &map);
+
+            // discover a corridor
+            map[3][3] = Some(tile(TileType::Grass));
+            planner.update(source, map.clone(), conditions()).unwrap();
+            assert_same_cost(&planner, source, &map);
+
+            // block a tile of the path
+            let path = planner.path().unwrap();
+            let mut position = source;
+            for action in path.actions.iter().take(4) {
+                if let Action::Go(direction) = action {
+                    position = get_next_coords(7, position, direction).unwrap();
+                }
+            }
+            map[position.0][position.1] = Some(tile(TileType::Wall));
+            planner.update(source, map.clone(), conditions()).unwrap();
+            assert_same_cost(&planner, source, &map);
+
+            // move the source
+            source = (2, 5);
+            planner.update(source, map.clone(), conditions()).unwrap();
+            assert_same_cost(&planner, source, &map);
+
+            // make a tile more expensive
+            map[3][6] = Some(tile(TileType::Hill));
+            planner.update(source, map.clone(), conditions()).unwrap();
+            assert_same_cost(&planner, source, &map);
+
+            // close every way to the destination
+            map[3][3] = Some(tile(TileType::Wall));
+            map[3][6] = Some(tile(TileType::Wall));
+            planner.update(source, map.clone(), conditions()).unwrap();
+            assert_same_cost(&planner, source, &map);
+        }
+    }
+
+    #[test]
+    fn repairs_the_search_after_random_map_changes() {
+        const TILE_TYPES: [TileType; 5] = [
+            TileType::Grass,
+            TileType::Sand,
+            TileType::Hill,
+            TileType::Wall,
+            TileType::Teleport(true),
+        ];
+
+        let mut rng = Rng::new(13);
+
+        for algorithm in [SearchAlgorithm::Dijkstra, SearchAlgorithm::AStar] {
+            let options = SearchOptions {
+                algorithm,
+                ..SearchOptions::default()
+            };
+            let destination = (11, 11);
+            let mut planner = Planner::new(destination, options);
+            let mut source = (0, 0);
+            let mut map = random_map(&mut rng, 12, 2);
+            map[source.0][source.1] = Some(tile(TileType::Grass));
+            map[destination.0][destination.1] = Some(tile(TileType::Grass));
+
+            for _ in 0..40 {
+                for _ in 0..1 + rng.next(3) {
+                    let (row, col) = (rng.next(12), rng.next(12));
+
+                    if (row, col) != source && (row, col) != destination {
+                        map[row][col] = match rng.next(6) {
+                            5 => None,
+                            tile_type => Some(tile(TILE_TYPES[tile_type])),
+                        };
+                    }
+                }
+
+                if rng.next(3) == 0 {
+                    let (row, col) = (rng.next(12), rng.next(12));
+                    map[row][col] = Some(tile(TileType::Grass));
+                    source = (row, col);
+                }
+
+                planner.update(source, map.clone(), conditions()).unwrap();
+                assert_same_cost(&planner, source, &map);
+            }
+        }
+    }
+}
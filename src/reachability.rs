@@ -0,0 +1,247 @@
+use std::collections::HashSet;
+
+use robotics_lib::world::environmental_conditions::EnvironmentalConditions;
+use robotics_lib::world::tile::Tile;
+
+use crate::dijkstra::{search, Graph, SearchTree};
+use crate::error::TomTomError;
+use crate::path::Path;
+use crate::search::SearchOptions;
+
+/// Reachability describes the tiles reachable from a source tile, computed with a single search over the whole known map.
+/// It keeps the map, the environmental conditions and a copy of the options of the search, so that path_to costs the actions
+/// as they were searched even after the robot map or the options of the caller change.
+///
+/// # Fields:
+/// - source: (usize, usize) => source tile of coordinates (row, col).
+/// - costs: Vec<Vec<Option<usize>>> => energy cost of the path reaching each tile of coordinates (row, col), None if the tile is unreachable
+///   or exceeds the maximum cost.
+pub struct Reachability {
+    pub source: (usize, usize),
+    pub costs: Vec<Vec<Option<usize>>>,
+    tree: SearchTree,
+    map: Vec<Vec<Option<Tile>>>,
+    environmental_conditions: EnvironmentalConditions,
+    options: SearchOptions,
+}
+
+impl Reachability {
+    /// cost returns the energy cost of the path reaching the tile.
+    ///
+    /// # Arguments
+    /// - coordinates: (usize, usize) => tile of coordinates (row, col).
+    ///
+    /// # Return
+    /// - Option<usize> => Some(cost) returns the cost, None means the tile is unreachable, exceeds the maximum cost or is out of bounds.
+    pub fn cost(&self, coordinates: (usize, usize)) -> Option<usize> {
+        *self.costs.get(coordinates.0)?.get(coordinates.1)?
+    }
+
+    /// reachable returns the coordinates (row, col) of the reachable tiles, ordered by energy cost.
+    ///
+    /// # Return
+    /// - Vec<(usize, usize)> => coordinates of the reachable tiles.
+    pub fn reachable(&self) -> Vec<(usize, usize)> {
+        let mut ret = Vec::new();
+
+        for (row, costs) in self.costs.iter().enumerate() {
+            for (col, cost) in costs.iter().enumerate() {
+                if let Some(cost) = cost {
+                    ret.push((*cost, (row, col)));
+                }
+            }
+        }
+
+        ret.sort();
+        ret.into_iter()
+            .map(|(_, coordinates)| coordinates)
+            .collect()
+    }
+
+    /// path_to returns the path reaching the destination tile, without searching again.
+    ///
+    /// # Arguments
+    /// - destination: (usize, usize) => destination tile of coordinates (row, col).
+    ///
+    /// # Return
+    /// - Result<Path, TomTomError> => Ok(path) returns the path, Err(e) represents a possible error described by TomTomError e.
+    pub fn path_to(&self, destination: (usize, usize)) -> Result<Path, TomTomError> {
+        let size = self.costs.len();
+
+        if destination.0 >= size || destination.1 >= size {
+            return Err(TomTomError::OutOfBounds(destination));
+        }
+
+        match self.cost(destination) {
+            None => Err(TomTomError::Unreachable),
+            Some(_) => {
+                let graph = Graph::new(&self.map, &self.environmental_conditions, &self.options);
+
+                self.tree
+                    .path_to(&graph, destination)
+                    .ok_or(TomTomError::Unreachable)
+            }
+        }
+    }
+}
+
+/// Costs stores the energy cost of reaching each tile of coordinates (row, col).
+type Costs = Vec<Vec<Option<usize>>>;
+
+/// search_costs returns the search tree from the source tile and the energy cost of reaching every tile,
+/// None if the tile is unreachable or its cost exceeds max_cost.
+fn search_costs(
+    graph: &Graph,
+    source: (usize, usize),
+    max_cost: Option<usize>,
+) -> Result<(SearchTree, Costs), TomTomError> {
+    let tree = search(graph, source, &HashSet::new())?;
+    let size = graph.size();
+
+    let costs = (0..size)
+        .map(|row| {
+            (0..size)
+                .map(|col| match (tree.cost((row, col)), max_cost) {
+                    (Some(cost), Some(max_cost)) if cost > max_cost => None,
+                    (cost, _) => cost,
+                })
+                .collect()
+        })
+        .collect();
+
+    Ok((tree, costs))
+}
+
+/// reachable_costs returns the energy cost of reaching every tile from the source tile, see search_costs.
+pub(crate) fn reachable_costs(
+    environmental_conditions: &EnvironmentalConditions,
+    map: &[Vec<Option<Tile>>],
+    source: (usize, usize),
+    max_cost: Option<usize>,
+    options: &SearchOptions,
+) -> Result<Vec<Vec<Option<usize>>>, TomTomError> {
+    let graph = Graph::new(map, environmental_conditions, options);
+
+    search_costs(&graph, source, max_cost).map(|(_, costs)| costs)
+}
+
+/// reachability returns the reachability from the source tile, taking the map and the environmental conditions it keeps.
+pub(crate) fn reachability(
+    environmental_conditions: EnvironmentalConditions,
+    map: Vec<Vec<Option<Tile>>>,
+    source: (usize, usize),
+    max_cost: Option<usize>,
+    options: &SearchOptions,
+) -> Result<Reachability, TomTomError> {
+    let graph = Graph::new(&map, &environmental_conditions, options);
+    let (tree, costs) = search_costs(&graph, source, max_cost)?;
+
+    Ok(Reachability {
+        source,
+        costs,
+        tree,
+        map,
+        environmental_conditions,
+        options: options.clone(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dijkstra::dijkstra;
+    use crate::test_utils::{conditions, map, random_map, Rng};
+
+    #[test]
+    fn tiles_beyond_the_maximum_cost_are_unreachable() {
+        let map = map(&[". . .", ". . .", ". . ."]);
+        let reachability = reachability(
+            conditions(),
+            map,
+            (0, 0),
+            Some(2),
+            &SearchOptions::default(),
+        )
+        .unwrap();
+
+        assert_eq!(reachability.cost((0, 0)), Some(0));
+        assert_eq!(reachability.cost((0, 2)), Some(2));
+        assert_eq!(reachability.cost((1, 1)), Some(2));
+        assert_eq!(reachability.cost((1, 2)), None);
+        assert_eq!(reachability.cost((2, 2)), None);
+        assert!(matches!(
+            reachability.path_to((2, 2)),
+            Err(TomTomError::Unreachable)
+        ));
+
+        let reachable = reachability.reachable();
+        assert_eq!(reachable.len(), 6);
+        assert_eq!(reachable[0], (0, 0));
+        assert!(reachable
+            .windows(2)
+            .all(|pair| reachability.cost(pair[0]) <= reachability.cost(pair[1])));
+    }
+
+    #[test]
+    fn unreachable_tiles_have_no_cost() {
+        let map = map(&[". # .", "# # ?", ". . ."]);
+        let reachability =
+            reachability(conditions(), map, (0, 0), None, &SearchOptions::default()).unwrap();
+
+        assert_eq!(reachability.reachable(), vec![(0, 0)]);
+        assert_eq!(reachability.cost((0, 2)), None);
+        assert_eq!(reachability.cost((1, 2)), None);
+        assert_eq!(reachability.cost((3, 0)), None);
+        assert!(matches!(
+            reachability.path_to((2, 2)),
+            Err(TomTomError::Unreachable)
+        ));
+        assert!(matches!(
+            reachability.path_to((3, 0)),
+            Err(TomTomError::OutOfBounds((3, 0)))
+        ));
+    }
+
+    #[test]
+    fn path_to_agrees_with_a_search_to_the_destination() {
+        let environmental_conditions = conditions();
+        let options = SearchOptions::default();
+        let mut rng = Rng::new(14);
+
+        for _ in 0..50 {
+            let teleports = rng.next(3);
+            let map = random_map(&mut rng, 8, teleports);
+            let source = (rng.next(8), rng.next(8));
+            let reachability = reachability(
+                environmental_conditions.clone(),
+                map.clone(),
+                source,
+                None,
+                &options,
+            )
+            .unwrap();
+
+            for _ in 0..5 {
+                let destination = (rng.next(8), rng.next(8));
+                let expected = dijkstra(
+                    &environmental_conditions,
+                    &map,
+                    source,
+                    HashSet::from([destination]),
+                    &options,
+                );
+
+                match (reachability.path_to(destination), expected) {
+                    (Ok(path), Ok(expected)) => {
+                        assert_eq!(path.cost, expected.cost);
+                        assert_eq!(path.cost, reachability.cost(destination).unwrap());
+                        assert_eq!(path.destination, destination);
+                        assert_eq!(path.breakdown.len(), path.actions.len());
+                    }
+                    (Err(TomTomError::Unreachable), Err(TomTomError::Unreachable)) => {}
+                    (actual, expected) => panic!("{actual:?} != {expected:?}"),
+                }
+            }
+        }
+    }
+}
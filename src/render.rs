@@ -0,0 +1,434 @@
+use robotics_lib::interface::Direction;
+use robotics_lib::world::tile::{Content, Tile, TileType};
+
+use crate::path::{Action, Path};
+
+/// TILE_SIZE is the side, in pixels, of a tile of the SVG map.
+const TILE_SIZE: usize = 16;
+
+/// PATH_COLOURS are the colours of the paths of the SVG map, used in order and starting again from the first one.
+const PATH_COLOURS: [&str; 8] = [
+    "#e6194b", "#ffe119", "#f58231", "#911eb4", "#46f0f0", "#f032e6", "#bcf60c", "#000075",
+];
+
+fn tile_glyph(tile_type: &TileType) -> char {
+    match tile_type {
+        TileType::DeepWater => '~',
+        TileType::ShallowWater => '-',
+        TileType::Sand => ':',
+        TileType::Grass => '.',
+        TileType::Street => '=',
+        TileType::Hill => '+',
+        TileType::Mountain => 'A',
+        TileType::Snow => '*',
+        TileType::Lava => '%',
+        TileType::Teleport(true) => 'O',
+        TileType::Teleport(false) => 'o',
+        TileType::Wall => '#',
+    }
+}
+
+fn tile_colour(tile_type: &TileType) -> &'static str {
+    match tile_type {
+        TileType::DeepWater => "#1f4e9c",
+        TileType::ShallowWater => "#4f8fd6",
+        TileType::Sand => "#e8d38a",
+        TileType::Grass => "#5fa343",
+        TileType::Street => "#8c8c8c",
+        TileType::Hill => "#8f7a4a",
+        TileType::Mountain => "#6b5a4a",
+        TileType::Snow => "#f2f4f7",
+        TileType::Lava => "#d8431d",
+        TileType::Teleport(true) => "#a24ccf",
+        TileType::Teleport(false) => "#5e3a73",
+        TileType::Wall => "#3a3a3a",
+    }
+}
+
+fn content_glyph(content: &Content) -> Option<char> {
+    match content {
+        Content::Rock(_) => Some('r'),
+        Content::Tree(_) => Some('t'),
+        Content::Garbage(_) => Some('g'),
+        Content::Fire => Some('f'),
+        Content::Coin(_) => Some('c'),
+        Content::Bin(_) => Some('b'),
+        Content::Crate(_) => Some('k'),
+        Content::Bank(_) => Some('$'),
+        Content::Water(_) => Some('w'),
+        Content::Market(_) => Some('m'),
+        Content::Fish(_) => Some('h'),
+        Content::Building => Some('u'),
+        Content::Bush(_) => Some('y'),
+        Content::JollyBlock(_) => Some('j'),
+        Content::Scarecrow => Some('s'),
+        Content::None => None,
+    }
+}
+
+fn action_glyph(action: &Action) -> char {
+    match action {
+        Action::Go(Direction::Up) => '^',
+        Action::Go(Direction::Down) => 'v',
+        Action::Go(Direction::Left) => '<',
+        Action::Go(Direction::Right) => '>',
+        Action::Teleport(_) => 'T',
+    }
+}
+
+/// render_ascii draws the map with one glyph per tile, rows separated by newlines and tiles by spaces.
+/// The path is drawn over the map with the glyph of the action leaving every tile, then the source, the destination and the robot.
+pub(crate) fn render_ascii(
+    map: &[Vec<Option<Tile>>],
+    robot: (usize, usize),
+    path: Option<&Path>,
+    contents: bool,
+) -> String {
+    let size = map.len();
+
+    let mut glyphs: Vec<Vec<char>> = map
+        .iter()
+        .map(|tiles| {
+            tiles
+                .iter()
+                .map(|tile| match tile {
+                    None => '?',
+                    Some(tile) => contents
+                        .then(|| content_glyph(&tile.content))
+                        .flatten()
+                        .unwrap_or_else(|| tile_glyph(&tile.tile_type)),
+                })
+                .collect()
+        })
+        .collect();
+
+    let mut overlay = |(row, col): (usize, usize), glyph: char| {
+        if row < size && col < size {
+            glyphs[row][col] = glyph;
+        }
+    };
+
+    if let Some(path) = path {
+        for (coordinates, action) in path.coordinates().zip(path.actions.iter()) {
+            overlay(coordinates, action_glyph(action));
+        }
+
+        overlay(path.source, 'S');
+        overlay(path.destination, 'X');
+    }
+
+    overlay(robot, '@');
+
+    let mut ret = String::with_capacity(size * size * 2);
+
+    for row in glyphs {
+        let row: Vec<String> = row.iter().map(char::to_string).collect();
+        ret.push_str(&row.join(" "));
+        ret.push('\n');
+    }
+
+    ret
+}
+
+/// center returns the coordinates (x, y), in pixels, of the center of the tile of coordinates (row, col).
+fn center((row, col): (usize, usize)) -> (usize, usize) {
+    (
+        col * TILE_SIZE + TILE_SIZE / 2,
+        row * TILE_SIZE + TILE_SIZE / 2,
+    )
+}
+
+/// render_svg draws the map as an SVG document: every tile is filled with the colour of its type and darkened by its elevation,
+/// then the optional costs are drawn as a heatmap from blue (cheapest) to red (most expensive) and the paths as lines of distinct colours.
+pub(crate) fn render_svg(
+    map: &[Vec<Option<Tile>>],
+    robot: (usize, usize),
+    paths: &[Path],
+    costs: Option<&[Vec<Option<usize>>]>,
+) -> String {
+    let size = map.len();
+    let side = size * TILE_SIZE;
+
+    let max_elevation = map
+        .iter()
+        .flatten()
+        .flatten()
+        .map(|tile| tile.elevation)
+        .max()
+        .unwrap_or(0);
+
+    let mut ret = format!(
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{side}\" height=\"{side}\" viewBox=\"0 0 {side} {side}\">\n"
+    );
+
+    for (row, tiles) in map.iter().enumerate() {
+        for (col, tile) in tiles.iter().enumerate() {
+            let (x, y) = (col * TILE_SIZE, row * TILE_SIZE);
+
+            match tile {
+                None => ret.push_str(&format!(
+                    "<rect x=\"{x}\" y=\"{y}\" width=\"{TILE_SIZE}\" height=\"{TILE_SIZE}\" fill=\"#202020\"/>\n"
+                )),
+                Some(tile) => {
+                    ret.push_str(&format!(
+                        "<rect x=\"{x}\" y=\"{y}\" width=\"{TILE_SIZE}\" height=\"{TILE_SIZE}\" fill=\"{}\"/>\n",
+                        tile_colour(&tile.tile_type)
+                    ));
+
+                    if tile.elevation > 0 {
+                        let opacity = 0.5 * tile.elevation as f64 / max_elevation as f64;
+
+                        ret.push_str(&format!(
+                            "<rect x=\"{x}\" y=\"{y}\" width=\"{TILE_SIZE}\" height=\"{TILE_SIZE}\" fill=\"#000000\" fill-opacity=\"{opacity:.2}\"/>\n"
+                        ));
+                    }
+                }
+            }
+        }
+    }
+
+    if let Some(costs) = costs {
+        let max_cost = costs.iter().flatten().flatten().max().copied().unwrap_or(0);
+
+        for (row, tiles_costs) in costs.iter().enumerate() {
+            for (col, cost) in tiles_costs.iter().enumerate() {
+                if let Some(cost) = cost {
+                    let (x, y) = (col * TILE_SIZE, row * TILE_SIZE);
+                    let hue = match max_cost {
+                        0 => 240.0,
+                        max_cost => 240.0 * (1.0 - *cost as f64 / max_cost as f64),
+                    };
+
+                    ret.push_str(&format!(
+                        "<rect x=\"{x}\" y=\"{y}\" width=\"{TILE_SIZE}\" height=\"{TILE_SIZE}\" fill=\"hsl({hue:.0}, 100%, 50%)\" fill-opacity=\"0.45\"><title>{cost}</title></rect>\n"
+                    ));
+                }
+            }
+        }
+    }
+
+    for (path, colour) in paths.iter().zip(PATH_COLOURS.iter().cycle()) {
+        let coordinates: Vec<(usize, usize)> = path.coordinates().collect();
+
+        for (action, (from, to)) in path
+            .actions
+            .iter()
+            .zip(coordinates.iter().zip(coordinates.iter().skip(1)))
+        {
+            let ((x1, y1), (x2, y2)) = (center(*from), center(*to));
+            let dash = match action {
+                Action::Teleport(_) => " stroke-dasharray=\"4 3\"",
+                Action::Go(_) => "",
+            };
+
+            ret.push_str(&format!(
+                "<line x1=\"{x1}\" y1=\"{y1}\" x2=\"{x2}\" y2=\"{y2}\" stroke=\"{colour}\" stroke-width=\"3\" stroke-linecap=\"round\"{dash}/>\n"
+            ));
+        }
+
+        let (x, y) = center(path.source);
+        ret.push_str(&format!(
+            "<circle cx=\"{x}\" cy=\"{y}\" r=\"{}\" fill=\"{colour}\" stroke=\"#ffffff\"/>\n",
+            TILE_SIZE / 4
+        ));
+
+        let (x, y) = (
+            path.destination.1 * TILE_SIZE + 3,
+            path.destination.0 * TILE_SIZE + 3,
+        );
+        ret.push_str(&format!(
+            "<rect x=\"{x}\" y=\"{y}\" width=\"{}\" height=\"{}\" fill=\"{colour}\" stroke=\"#ffffff\"/>\n",
+            TILE_SIZE - 6,
+            TILE_SIZE - 6
+        ));
+    }
+
+    if robot.0 < size && robot.1 < size {
+        let (x, y) = center(robot);
+        ret.push_str(&format!(
+            "<circle cx=\"{x}\" cy=\"{y}\" r=\"{}\" fill=\"#ffffff\" stroke=\"#000000\" stroke-width=\"2\"/>\n",
+            TILE_SIZE / 3
+        ));
+    }
+
+    ret.push_str("</svg>\n");
+
+    ret
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::map;
+    use crate::utils::get_action_destination;
+
+    fn path(source: (usize, usize), actions: &[Action]) -> Path {
+        let mut path = Path::new(source, source, 0);
+
+        for action in actions {
+            path.destination =
+                get_action_destination(usize::MAX, path.destination, action).unwrap();
+            path.actions.push_back(action.clone());
+        }
+
+        path
+    }
+
+    fn rocky_map() -> Vec<Vec<Option<Tile>>> {
+        let mut map = map(&[". : + ?", "O . # .", ". . . .", "~ . . ."]);
+        map[0][1].as_mut().unwrap().content = Content::Rock(2);
+        map
+    }
+
+    #[test]
+    fn ascii_draws_tiles_contents_and_undiscovered_tiles() {
+        let map = rocky_map();
+
+        assert_eq!(
+            render_ascii(&map, (9, 9), None, false),
+            ". : + ?\nO . # .\n. . . .\n~ . . .\n"
+        );
+        assert_eq!(
+            render_ascii(&map, (9, 9), None, true),
+            ". r + ?\nO . # .\n. . . .\n~ . . .\n"
+        );
+    }
+
+    #[test]
+    fn ascii_draws_the_path_then_source_destination_and_robot() {
+        let map = rocky_map();
+        let walk = path(
+            (2, 0),
+            &[
+                Action::Go(Direction::Right),
+                Action::Go(Direction::Up),
+                Action::Go(Direction::Up),
+                Action::Go(Direction::Right),
+            ],
+        );
+
+        assert_eq!(
+            render_ascii(&map, (2, 1), Some(&walk), true),
+            ". > X ?\nO ^ # .\nS @ . .\n~ . . .\n"
+        );
+
+        let teleport = path(
+            (2, 0),
+            &[Action::Go(Direction::Up), Action::Teleport((3, 3))],
+        );
+
+        assert_eq!(
+            render_ascii(&map, (2, 0), Some(&teleport), false),
+            ". : + ?\nT . # .\n@ . . .\n~ . . X\n"
+        );
+
+        let back = path(
+            (1, 1),
+            &[Action::Go(Direction::Up), Action::Go(Direction::Down)],
+        );
+
+        assert_eq!(
+            render_ascii(&map, (9, 9), Some(&back), false),
+            ". v + ?\nO X # .\n. . . .\n~ . . .\n"
+        );
+    }
+
+    #[test]
+    fn ascii_ignores_the_overlays_out_of_bounds() {
+        let map = rocky_map();
+        let leaving = path((3, 3), &[Action::Go(Direction::Right)]);
+
+        assert_eq!(
+            render_ascii(&map, (4, 0), Some(&leaving), false),
+            ". : + ?\nO . # .\n. . . .\n~ . . S\n"
+        );
+    }
+
+    /// titles returns the values of the title elements of the SVG document, in order.
+    fn titles(svg: &str) -> Vec<usize> {
+        svg.split("<title>")
+            .skip(1)
+            .map(|title| title.split("</title>").next().unwrap().parse().unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn svg_draws_a_rect_for_every_tile() {
+        let mut map = rocky_map();
+        let svg = render_svg(&map, (9, 9), &[], None);
+
+        assert!(svg.starts_with(
+            "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"64\" height=\"64\" viewBox=\"0 0 64 64\">\n"
+        ));
+        assert!(svg.ends_with("</svg>\n"));
+        assert_eq!(svg.matches("<rect").count(), 16);
+        assert_eq!(svg.matches("fill=\"#202020\"").count(), 1);
+        assert!(!svg.contains("<circle"));
+
+        map[1][1].as_mut().unwrap().elevation = 2;
+        map[2][2].as_mut().unwrap().elevation = 4;
+        let svg = render_svg(&map, (0, 0), &[], None);
+
+        assert_eq!(svg.matches("<rect").count(), 18);
+        assert!(svg.contains("fill-opacity=\"0.25\""));
+        assert!(svg.contains("fill-opacity=\"0.50\""));
+        assert_eq!(svg.matches("<circle").count(), 1);
+    }
+
+    #[test]
+    fn svg_draws_the_costs_as_a_heatmap() {
+        let map = rocky_map();
+        let costs = vec![
+            vec![Some(0), Some(3), None, None],
+            vec![Some(1), Some(2), None, None],
+            vec![None, None, None, None],
+            vec![None, None, None, Some(6)],
+        ];
+
+        let svg = render_svg(&map, (9, 9), &[], Some(&costs));
+
+        assert_eq!(titles(&svg), vec![0, 3, 1, 2, 6]);
+        assert_eq!(svg.matches("<rect").count(), 16 + 5);
+        assert!(svg.contains("fill=\"hsl(240, 100%, 50%)\" fill-opacity=\"0.45\"><title>0</title>"));
+        assert!(svg.contains("fill=\"hsl(0, 100%, 50%)\" fill-opacity=\"0.45\"><title>6</title>"));
+    }
+
+    #[test]
+    fn svg_draws_the_teleports_dashed() {
+        let map = rocky_map();
+        let paths = [
+            path(
+                (2, 0),
+                &[Action::Go(Direction::Up), Action::Teleport((3, 3))],
+            ),
+            path(
+                (2, 0),
+                &[Action::Go(Direction::Right), Action::Go(Direction::Right)],
+            ),
+        ];
+
+        let svg = render_svg(&map, (9, 9), &paths, None);
+
+        assert_eq!(svg.matches("<line").count(), 4);
+        assert_eq!(svg.matches("stroke-dasharray").count(), 1);
+        assert!(svg.contains(&format!(
+            "<line x1=\"8\" y1=\"24\" x2=\"56\" y2=\"56\" stroke=\"{}\" stroke-width=\"3\" stroke-linecap=\"round\" stroke-dasharray=\"4 3\"/>",
+            PATH_COLOURS[0]
+        )));
+        assert_eq!(svg.matches(PATH_COLOURS[1]).count(), 2 + 2);
+        assert_eq!(svg.matches("<circle").count(), 2);
+    }
+
+    #[test]
+    fn svg_has_no_nan_with_zero_costs_and_elevations() {
+        let map = rocky_map();
+        let costs = vec![vec![Some(0); 4]; 4];
+
+        let svg = render_svg(&map, (0, 0), &[], Some(&costs));
+
+        assert!(!svg.contains("NaN"));
+        assert!(!svg.contains("inf"));
+        assert_eq!(titles(&svg), vec![0; 16]);
+        assert_eq!(svg.matches("hsl(240, 100%, 50%)").count(), 16);
+    }
+}
@@ -0,0 +1,55 @@
+use std::rc::Rc;
+
+use crate::constraints::Constraints;
+use crate::cost::{CostModel, DefaultCostModel};
+use crate::forecast::Forecast;
+
+/// SearchAlgorithm enumerates the algorithms that can be used to search a path.
+///
+/// # Variants:
+/// - Dijkstra => expands the known map in order of energy cost from the source tile.
+/// - AStar => expands the known map guided by an admissible estimate of the remaining energy cost to the targets,
+///   returning a path of the same cost as Dijkstra while usually expanding far fewer tiles.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SearchAlgorithm {
+    #[default]
+    Dijkstra,
+    AStar,
+}
+
+/// SearchOptions describes how TomTom searches a path.
+///
+/// # Fields:
+/// - algorithm: SearchAlgorithm => algorithm used to search the path.
+/// - replan_limit: usize => maximum number of times the go_to functions search the path again from the robot's coordinates,
+///   when the cost or walkability of the next action changed in the robot map while executing (0 never searches again).
+/// - cost_model: Rc<dyn CostModel> => cost model of the actions, DefaultCostModel by default.
+/// - constraints: Constraints => tiles the path must avoid and tiles the path should avoid when possible.
+/// - unknown_cost: Option<usize> => if Some(cost), undiscovered tiles are considered walkable and every action from or to them costs cost,
+///   producing tentative paths (see Path::unknown_steps); if None (default), undiscovered tiles are never crossed.
+/// - forecast: Option<Rc<dyn Forecast>> => if Some(forecast), every go action is costed with the environmental conditions expected at its step,
+///   for robots performing a path over several ticks (the go_to functions perform the whole path in the current tick);
+///   the search keeps a state for every tile and step up to the horizon of the forecast, so it returns the path of smallest cost under the forecast,
+///   and SearchAlgorithm::AStar bounds the go costs with the cheapest conditions forecast. If None (default), the current conditions are used.
+#[derive(Clone)]
+pub struct SearchOptions {
+    pub algorithm: SearchAlgorithm,
+    pub replan_limit: usize,
+    pub cost_model: Rc<dyn CostModel>,
+    pub constraints: Constraints,
+    pub unknown_cost: Option<usize>,
+    pub forecast: Option<Rc<dyn Forecast>>,
+}
+
+impl Default for SearchOptions {
+    fn default() -> Self {
+        SearchOptions {
+            algorithm: SearchAlgorithm::default(),
+            replan_limit: 0,
+            cost_model: Rc::new(DefaultCostModel),
+            constraints: Constraints::default(),
+            unknown_cost: None,
+            forecast: None,
+        }
+    }
+}
@@ -0,0 +1,117 @@
+use robotics_lib::world::environmental_conditions::{EnvironmentalConditions, WeatherType};
+use robotics_lib::world::tile::{Content, Tile, TileType};
+
+use crate::forecast::Forecast;
+
+/// conditions returns sunny environmental conditions at noon.
+pub(crate) fn conditions() -> EnvironmentalConditions {
+    EnvironmentalConditions::new(&[WeatherType::Sunny], 15, 12).unwrap()
+}
+
+/// RainFrom forecasts sunny weather at noon before the given step and rain from it on.
+pub(crate) struct RainFrom(pub(crate) usize);
+
+impl Forecast for RainFrom {
+    fn conditions(&self, step: usize) -> Option<EnvironmentalConditions> {
+        let weather = match step < self.0 {
+            true => WeatherType::Sunny,
+            false => WeatherType::Rainy,
+        };
+
+        EnvironmentalConditions::new(&[weather], 15, 12).ok()
+    }
+
+    fn horizon(&self) -> usize {
+        self.0
+    }
+}
+
+pub(crate) fn tile(tile_type: TileType) -> Tile {
+    Tile {
+        tile_type,
+        content: Content::None,
+        elevation: 0,
+    }
+}
+
+/// map returns the square map drawn by the rows, with the glyphs of render_ascii: ? is an undiscovered tile.
+pub(crate) fn map(rows: &[&str]) -> Vec<Vec<Option<Tile>>> {
+    rows.iter()
+        .map(|row| {
+            row.chars()
+                .filter(|glyph| !glyph.is_whitespace())
+                .map(|glyph| {
+                    let tile_type = match glyph {
+                        '~' => TileType::DeepWater,
+                        '-' => TileType::ShallowWater,
+                        ':' => TileType::Sand,
+                        '.' => TileType::Grass,
+                        '=' => TileType::Street,
+                        '+' => TileType::Hill,
+                        'A' => TileType::Mountain,
+                        '*' => TileType::Snow,
+                        '%' => TileType::Lava,
+                        'O' => TileType::Teleport(true),
+                        'o' => TileType::Teleport(false),
+                        '#' => TileType::Wall,
+                        '?' => return None,
+                        glyph => panic!("unknown glyph {glyph}"),
+                    };
+
+                    Some(tile(tile_type))
+                })
+                .collect()
+        })
+        .collect()
+}
+
+/// Rng is a xorshift generator, so that the random maps are the same at every run.
+pub(crate) struct Rng(u64);
+
+impl Rng {
+    pub(crate) fn new(seed: u64) -> Rng {
+        Rng(seed.wrapping_mul(0x9e37_79b9_7f4a_7c15) | 1)
+    }
+
+    pub(crate) fn next(&mut self, bound: usize) -> usize {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 7;
+        self.0 ^= self.0 << 17;
+        (self.0 % bound as u64) as usize
+    }
+}
+
+/// random_map returns a discovered map of mixed walkable tiles with walls, elevations and the given number of active teleports.
+pub(crate) fn random_map(rng: &mut Rng, size: usize, teleports: usize) -> Vec<Vec<Option<Tile>>> {
+    const TILE_TYPES: [TileType; 8] = [
+        TileType::Grass,
+        TileType::Grass,
+        TileType::Street,
+        TileType::Sand,
+        TileType::Hill,
+        TileType::ShallowWater,
+        TileType::Snow,
+        TileType::Wall,
+    ];
+
+    let mut map: Vec<Vec<Option<Tile>>> = (0..size)
+        .map(|_| {
+            (0..size)
+                .map(|_| {
+                    Some(Tile {
+                        tile_type: TILE_TYPES[rng.next(TILE_TYPES.len())],
+                        content: Content::None,
+                        elevation: rng.next(4),
+                    })
+                })
+                .collect()
+        })
+        .collect();
+
+    for _ in 0..teleports {
+        let (row, col) = (rng.next(size), rng.next(size));
+        map[row][col] = Some(tile(TileType::Teleport(true)));
+    }
+
+    map
+}
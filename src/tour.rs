@@ -0,0 +1,1020 @@
+use std::collections::{HashMap, HashSet};
+
+use robotics_lib::world::environmental_conditions::EnvironmentalConditions;
+use robotics_lib::world::tile::Tile;
+
+use crate::dijkstra::{dijkstra, search, Graph, SearchTree};
+use crate::error::TomTomError;
+use crate::path::Path;
+use crate::search::SearchOptions;
+use crate::utils::get_targets;
+
+/// Up to this number of stops the tour order is computed exactly, beyond it heuristics are used.
+const EXACT_STOPS_LIMIT: usize = 10;
+
+/// Passes of 2-opt shortening the order built by the budgeted insertion, when no stop fits the budget anymore.
+const TWO_OPT_PASSES: usize = 3;
+
+/// Tour describes the sequence of paths visiting a set of tiles, starting from the robot's tile.
+///
+/// # Fields:
+/// - paths: Vec<Path> => paths to be performed in order, each one starting from the destination tile of the previous one.
+/// - cost: usize => energy cost of the whole tour.
+/// - value: usize => total value of the visited tiles (each tile is worth 1 unless a value is specified).
+/// - unreachable: Vec<(usize, usize)> => coordinates (row, col) of the requested tiles that can't be reached and are not part of the tour.
+#[derive(Debug, Default, Clone)]
+pub struct Tour {
+    pub paths: Vec<Path>,
+    pub cost: usize,
+    pub value: usize,
+    pub unreachable: Vec<(usize, usize)>,
+}
+
+/// Budget describes the energy a budgeted tour can consume.
+///
+/// # Fields:
+/// - energy: Option<usize> => energy the tour must fit, if None the robot's energy level.
+/// - return_to_source: bool => if true the tour goes back to the robot's tile, and the way back fits the energy too.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct Budget {
+    pub energy: Option<usize>,
+    pub return_to_source: bool,
+}
+
+/// Stop describes a tile to be visited by a tour, together with the tiles whose reaching counts as visiting it.
+pub(crate) struct Stop {
+    pub(crate) coordinates: (usize, usize),
+    pub(crate) targets: HashSet<(usize, usize)>,
+    pub(crate) value: usize,
+}
+
+/// get_stops returns the stops visiting the destination tiles, or their adjacent tiles if adjacent is true.
+pub(crate) fn get_stops(
+    map: &[Vec<Option<Tile>>],
+    adjacent: bool,
+    destinations: &[((usize, usize), usize)],
+) -> Vec<Stop> {
+    destinations
+        .iter()
+        .map(|(destination, value)| Stop {
+            coordinates: *destination,
+            targets: get_targets(map, adjacent, [*destination]),
+            value: *value,
+        })
+        .collect()
+}
+
+/// Legs stores the measure (search distance or energy cost) of the legs between the tiles where a tour can be: the source tile (index 0)
+/// and the targets of every stop (the following indexes), so that every leg starts from the target reached by the previous leg.
+/// For every tile it stores the stop it belongs to (None for the source), and for every stop the indexes of its targets.
+pub(crate) struct Legs {
+    tiles: Vec<(usize, usize)>,
+    stops: Vec<Option<usize>>,
+    targets: Vec<Vec<usize>>,
+    matrix: Vec<Vec<Option<usize>>>,
+}
+
+impl Legs {
+    fn measure(&self, from: usize, to: usize) -> usize {
+        self.matrix[from][to].unwrap_or(usize::MAX)
+    }
+
+    /// is_reachable returns true if a target of the stop can be reached from the source and, if return_to_source is true,
+    /// the source can be reached from it.
+    fn is_reachable(&self, stop: usize, return_to_source: bool) -> bool {
+        self.targets[stop].iter().any(|target| {
+            self.matrix[0][*target].is_some()
+                && (!return_to_source || self.matrix[*target][0].is_some())
+        })
+    }
+}
+
+/// calculate_legs returns the legs of the given measure between the source and the targets of the stops, None meaning the tile
+/// can't be reached. The legs from a tile are searched once, and only if the tile can be reached from the source.
+pub(crate) fn calculate_legs(
+    environmental_conditions: &EnvironmentalConditions,
+    map: &[Vec<Option<Tile>>],
+    source: (usize, usize),
+    stops: &[Stop],
+    options: &SearchOptions,
+    measure: fn(&SearchTree, (usize, usize)) -> Option<usize>,
+) -> Result<Legs, TomTomError> {
+    let mut tiles = vec![source];
+    let mut tiles_stops = vec![None];
+    let mut targets = Vec::new();
+
+    for (
+        stop,
+        Stop {
+            targets: stop_targets,
+            ..
+        },
+    ) in stops.iter().enumerate()
+    {
+        let mut stop_targets: Vec<(usize, usize)> = stop_targets.iter().copied().collect();
+        stop_targets.sort();
+
+        targets.push((tiles.len()..tiles.len() + stop_targets.len()).collect());
+        tiles_stops.extend(stop_targets.iter().map(|_| Some(stop)));
+        tiles.extend(stop_targets);
+    }
+
+    let graph = Graph::new(map, environmental_conditions, options);
+    let mut rows: HashMap<(usize, usize), Vec<Option<usize>>> = HashMap::new();
+    let mut matrix: Vec<Vec<Option<usize>>> = Vec::with_capacity(tiles.len());
+
+    for (from, tile) in tiles.iter().enumerate() {
+        let row = match rows.get(tile) {
+            Some(row) => row.clone(),
+            None if from > 0 && matrix[0][from].is_none() => vec![None; tiles.len()],
+            None => {
+                let tree = search(&graph, *tile, &HashSet::new())?;
+                let row: Vec<Option<usize>> = tiles.iter().map(|to| measure(&tree, *to)).collect();
+
+                rows.insert(*tile, row.clone());
+                row
+            }
+        };
+
+        matrix.push(row);
+    }
+
+    Ok(Legs {
+        tiles,
+        stops: tiles_stops,
+        targets,
+        matrix,
+    })
+}
+
+/// calculate_paths returns the tour performing the paths to the target tiles (indexes of the legs) in the given order,
+/// then back to the source (the tile of index 0) if return_to_source is true.
+pub(crate) fn calculate_paths(
+    environmental_conditions: &EnvironmentalConditions,
+    map: &[Vec<Option<Tile>>],
+    stops: &[Stop],
+    legs: &Legs,
+    order: &[usize],
+    return_to_source: bool,
+    options: &SearchOptions,
+) -> Result<Tour, TomTomError> {
+    let source = legs.tiles[0];
+    let mut tour = Tour::default();
+    let mut position = source;
+
+    for tile in order {
+        let path = dijkstra(
+            environmental_conditions,
+            map,
+            position,
+            HashSet::from([legs.tiles[*tile]]),
+            options,
+        )?;
+
+        position = path.destination;
+        tour.cost += path.cost;
+        tour.value += legs.stops[*tile].map_or(0, |stop| stops[stop].value);
+        tour.paths.push(path);
+    }
+
+    if return_to_source && position != source {
+        let path = dijkstra(
+            environmental_conditions,
+            map,
+            position,
+            HashSet::from([source]),
+            options,
+        )?;
+
+        tour.cost += path.cost;
+        tour.paths.push(path);
+    }
+
+    Ok(tour)
+}
+
+/// tour returns the tour visiting every reachable stop, ordered to minimize the total search distance.
+pub(crate) fn tour(
+    environmental_conditions: &EnvironmentalConditions,
+    map: &[Vec<Option<Tile>>],
+    source: (usize, usize),
+    stops: Vec<Stop>,
+    options: &SearchOptions,
+) -> Result<Tour, TomTomError> {
+    if stops.is_empty() {
+        return Err(TomTomError::NoTargets);
+    }
+
+    let legs = calculate_legs(
+        environmental_conditions,
+        map,
+        source,
+        &stops,
+        options,
+        SearchTree::distance,
+    )?;
+
+    let (reachable, unreachable): (Vec<usize>, Vec<usize>) =
+        (0..stops.len()).partition(|stop| legs.is_reachable(*stop, false));
+
+    if reachable.is_empty() {
+        return Err(TomTomError::Unreachable);
+    }
+
+    let order = if reachable.len() <= EXACT_STOPS_LIMIT {
+        let held_karp = held_karp(&legs, &reachable);
+        let full = (1 << reachable.len()) - 1;
+
+        match (1..legs.tiles.len())
+            .filter(|last| held_karp.costs[full][*last] != usize::MAX)
+            .min_by_key(|last| held_karp.costs[full][*last])
+        {
+            None => return Err(TomTomError::Unreachable),
+            Some(last) => reconstruct(&held_karp, full, last),
+        }
+    } else {
+        let order = two_opt(
+            &legs,
+            nearest_neighbour(&legs, &reachable),
+            false,
+            usize::MAX,
+        );
+        choose_targets(&legs, &order, false).1
+    };
+
+    let mut tour = calculate_paths(
+        environmental_conditions,
+        map,
+        &stops,
+        &legs,
+        &order,
+        false,
+        options,
+    )?;
+    tour.unreachable = unreachable
+        .into_iter()
+        .map(|stop| stops[stop].coordinates)
+        .collect();
+
+    Ok(tour)
+}
+
+/// budgeted_tour returns the tour visiting the subset of the reachable stops having the greatest total value
+/// whose energy cost, including the way back to the source if return_to_source is true, fits the budget.
+pub(crate) fn budgeted_tour(
+    environmental_conditions: &EnvironmentalConditions,
+    map: &[Vec<Option<Tile>>],
+    source: (usize, usize),
+    stops: Vec<Stop>,
+    budget: usize,
+    return_to_source: bool,
+    options: &SearchOptions,
+) -> Result<Tour, TomTomError> {
+    if stops.is_empty() {
+        return Err(TomTomError::NoTargets);
+    }
+
+    let legs = calculate_legs(
+        environmental_conditions,
+        map,
+        source,
+        &stops,
+        options,
+        SearchTree::cost,
+    )?;
+
+    let (reachable, unreachable): (Vec<usize>, Vec<usize>) =
+        (0..stops.len()).partition(|stop| legs.is_reachable(*stop, return_to_source));
+
+    let values: Vec<usize> = stops.iter().map(|stop| stop.value).collect();
+
+    let mut order: Vec<usize> = if reachable.len() <= EXACT_STOPS_LIMIT {
+        solve_budgeted_exact(&legs, &values, &reachable, budget, return_to_source)
+    } else {
+        let order = solve_budgeted_insertion(&legs, &values, &reachable, budget, return_to_source);
+        choose_targets(&legs, &order, return_to_source).1
+    };
+
+    // The legs are the energy costs of the paths of smallest search distance, which may differ from the searched paths' costs
+    // when paths of equal search distance have different penalties: drop the last stops until the actual paths fit the budget.
+    let mut tour = loop {
+        let tour = calculate_paths(
+            environmental_conditions,
+            map,
+            &stops,
+            &legs,
+            &order,
+            return_to_source,
+            options,
+        )?;
+
+        if tour.cost <= budget || order.is_empty() {
+            break tour;
+        }
+
+        order.pop();
+    };
+
+    tour.unreachable = unreachable
+        .into_iter()
+        .map(|stop| stops[stop].coordinates)
+        .collect();
+
+    Ok(tour)
+}
+
+/// choose_targets returns the smallest measure visiting the stops in the given order, starting from the source and going back to it
+/// if return_to_source is true, together with the target tile reached for every stop.
+fn choose_targets(legs: &Legs, order: &[usize], return_to_source: bool) -> (usize, Vec<usize>) {
+    // for every stop, for every target: (smallest measure reaching it, index of the previous stop's target)
+    let mut layers: Vec<Vec<(usize, usize)>> = Vec::with_capacity(order.len());
+    let mut previous_tiles: &[usize] = &[0];
+    let mut previous_measures: Vec<usize> = vec![0];
+
+    for stop in order {
+        let tiles = &legs.targets[*stop];
+
+        let layer: Vec<(usize, usize)> = tiles
+            .iter()
+            .map(|tile| {
+                previous_tiles
+                    .iter()
+                    .zip(previous_measures.iter())
+                    .enumerate()
+                    .map(|(index, (previous, measure))| {
+                        (
+                            measure.saturating_add(legs.measure(*previous, *tile)),
+                            index,
+                        )
+                    })
+                    .min()
+                    .unwrap_or((usize::MAX, 0))
+            })
+            .collect();
+
+        previous_measures = layer.iter().map(|(measure, _)| *measure).collect();
+        previous_tiles = tiles;
+        layers.push(layer);
+    }
+
+    let (total, mut index) = previous_tiles
+        .iter()
+        .zip(previous_measures.iter())
+        .enumerate()
+        .map(|(index, (tile, measure))| match return_to_source {
+            true => (measure.saturating_add(legs.measure(*tile, 0)), index),
+            false => (*measure, index),
+        })
+        .min()
+        .unwrap_or((usize::MAX, 0));
+
+    let mut tiles = vec![0; order.len()];
+
+    for (position, stop) in order.iter().enumerate().rev() {
+        tiles[position] = legs.targets[*stop][index];
+        index = layers[position][index].1;
+    }
+
+    (total, tiles)
+}
+
+/// order_measure returns the total measure of visiting the stops in order, starting from the source
+/// and going back to it if return_to_source is true.
+fn order_measure(legs: &Legs, order: &[usize], return_to_source: bool) -> usize {
+    choose_targets(legs, order, return_to_source).0
+}
+
+/// HeldKarp stores, for every subset (mask) of the stops and every tile (last), the smallest measure visiting the subset starting
+/// from the source and ending in the tile, a target of a stop of the subset, together with the previous tile (0 for the source).
+/// The bit of every tile is the bit of its stop in the masks.
+struct HeldKarp {
+    costs: Vec<Vec<usize>>,
+    previous: Vec<Vec<usize>>,
+    bits: Vec<Option<usize>>,
+}
+
+/// held_karp computes the HeldKarp table of the stops, whose bits are their indexes in stops.
+fn held_karp(legs: &Legs, stops: &[usize]) -> HeldKarp {
+    let n = stops.len();
+    let size = legs.tiles.len();
+
+    let mut bits = vec![None; size];
+    let mut costs = vec![vec![usize::MAX; size]; 1 << n];
+    let mut previous = vec![vec![0; size]; 1 << n];
+
+    for (bit, stop) in stops.iter().enumerate() {
+        for tile in legs.targets[*stop].iter() {
+            bits[*tile] = Some(bit);
+            costs[1 << bit][*tile] = legs.measure(0, *tile);
+        }
+    }
+
+    for mask in 1..(1 << n) {
+        for last in 1..size {
+            if costs[mask][last] == usize::MAX {
+                continue;
+            }
+
+            for (bit, stop) in stops.iter().enumerate() {
+                if mask & (1 << bit) != 0 {
+                    continue;
+                }
+
+                let next_mask = mask | (1 << bit);
+
+                for next in legs.targets[*stop].iter() {
+                    let cost = costs[mask][last].saturating_add(legs.measure(last, *next));
+
+                    if cost < costs[next_mask][*next] {
+                        costs[next_mask][*next] = cost;
+                        previous[next_mask][*next] = last;
+                    }
+                }
+            }
+        }
+    }
+
+    HeldKarp {
+        costs,
+        previous,
+        bits,
+    }
+}
+
+/// reconstruct returns the tiles visiting the subset (mask) and ending in the last tile, following the previous tiles.
+fn reconstruct(held_karp: &HeldKarp, mut mask: usize, mut last: usize) -> Vec<usize> {
+    let mut order = Vec::new();
+
+    while mask != 0 {
+        let bit = match held_karp.bits[last] {
+            Some(bit) => bit,
+            None => break,
+        };
+
+        order.push(last);
+        let before = held_karp.previous[mask][last];
+        mask &= !(1 << bit);
+        last = before;
+    }
+
+    order.reverse();
+    order
+}
+
+/// nearest_neighbour returns the order of the stops visiting, each time, the stop having the nearest target not visited yet.
+fn nearest_neighbour(legs: &Legs, stops: &[usize]) -> Vec<usize> {
+    let mut remaining = stops.to_vec();
+    let mut order = Vec::new();
+    let mut current: usize = 0;
+
+    while let Some((index, tile)) = remaining
+        .iter()
+        .enumerate()
+        .flat_map(|(index, stop)| legs.targets[*stop].iter().map(move |tile| (index, *tile)))
+        .min_by_key(|(_, tile)| legs.measure(current, *tile))
+    {
+        order.push(remaining.swap_remove(index));
+        current = tile;
+    }
+
+    order
+}
+
+/// two_opt improves the order of the stops by reversing segments of it, as long as the total measure decreases
+/// and for up to the given number of passes. Every pass measures O(n²) orders of n stops.
+fn two_opt(
+    legs: &Legs,
+    mut order: Vec<usize>,
+    return_to_source: bool,
+    passes: usize,
+) -> Vec<usize> {
+    let mut best = order_measure(legs, &order, return_to_source);
+    let mut improved = true;
+
+    for _ in 0..passes {
+        if !improved {
+            break;
+        }
+
+        improved = false;
+
+        for i in 0..order.len() {
+            for j in (i + 1)..order.len() {
+                order[i..=j].reverse();
+                let candidate = order_measure(legs, &order, return_to_source);
+
+                if candidate < best {
+                    best = candidate;
+                    improved = true;
+                } else {
+                    order[i..=j].reverse();
+                }
+            }
+        }
+    }
+
+    order
+}
+
+/// solve_budgeted_exact returns the tiles visiting the subset of stops with the greatest total value fitting the budget,
+/// preferring the cheapest one among equally valuable subsets.
+fn solve_budgeted_exact(
+    legs: &Legs,
+    values: &[usize],
+    stops: &[usize],
+    budget: usize,
+    return_to_source: bool,
+) -> Vec<usize> {
+    let held_karp = held_karp(legs, stops);
+
+    // (value, cost, mask, last)
+    let mut best: Option<(usize, usize, usize, usize)> = None;
+
+    for (mask, row) in held_karp.costs.iter().enumerate().skip(1) {
+        let value: usize = (0..stops.len())
+            .filter(|bit| mask & (1 << bit) != 0)
+            .map(|bit| values[stops[bit]])
+            .sum();
+
+        for (last, cost) in row.iter().enumerate() {
+            if *cost == usize::MAX {
+                continue;
+            }
+
+            let mut cost = *cost;
+
+            if return_to_source {
+                cost = cost.saturating_add(legs.measure(last, 0));
+            }
+
+            if cost > budget {
+                continue;
+            }
+
+            let better = match best {
+                None => true,
+                Some((best_value, best_cost, _, _)) => {
+                    value > best_value || (value == best_value && cost < best_cost)
+                }
+            };
+
+            if better {
+                best = Some((value, cost, mask, last));
+            }
+        }
+    }
+
+    match best {
+        None => Vec::new(),
+        Some((_, _, mask, last)) => reconstruct(&held_karp, mask, last),
+    }
+}
+
+/// solve_budgeted_insertion returns the order of a subset of stops fitting the budget, built by repeatedly inserting the stop
+/// with the greatest value per added measure in its cheapest position and target, keeping the targets of the stops already inserted.
+/// When no stop fits anymore, the order is shortened once with up to TWO_OPT_PASSES passes of 2-opt and the insertions resume.
+///
+/// With n stops of up to k targets each, an insertion tries every remaining stop, position and target measuring only the legs it adds
+/// and the one it replaces, in O(n² · k): the insertions take O(n³ · k) and every pass of 2-opt O(n³ · k²).
+fn solve_budgeted_insertion(
+    legs: &Legs,
+    values: &[usize],
+    stops: &[usize],
+    budget: usize,
+    return_to_source: bool,
+) -> Vec<usize> {
+    let mut order: Vec<usize> = Vec::new();
+    let mut tiles: Vec<usize> = Vec::new();
+    let mut current: usize = 0;
+    let mut remaining: Vec<usize> = stops
+        .iter()
+        .copied()
+        .filter(|stop| values[*stop] > 0)
+        .collect();
+    let mut shortened = false;
+
+    loop {
+        // (value, added measure, index in remaining, position in order, target tile)
+        let mut best: Option<(usize, usize, usize, usize, usize)> = None;
+
+        for (index, stop) in remaining.iter().enumerate() {
+            let value = values[*stop];
+
+            for position in 0..=tiles.len() {
+                let previous = if position == 0 {
+                    0
+                } else {
+                    tiles[position - 1]
+                };
+                let next = match tiles.get(position) {
+                    Some(next) => Some(*next),
+                    None if return_to_source => Some(0),
+                    None => None,
+                };
+
+                for tile in legs.targets[*stop].iter() {
+                    let added = match next {
+                        None => legs.measure(previous, *tile),
+                        Some(next) => legs
+                            .measure(previous, *tile)
+                            .saturating_add(legs.measure(*tile, next))
+                            .saturating_sub(legs.measure(previous, next)),
+                    };
+
+                    if current.saturating_add(added) > budget {
+                        continue;
+                    }
+
+                    let better = match best {
+                        None => true,
+                        Some((best_value, best_added, _, _, _)) => {
+                            value as u128 * (best_added as u128 + 1)
+                                > best_value as u128 * (added as u128 + 1)
+                        }
+                    };
+
+                    if better {
+                        best = Some((value, added, index, position, *tile));
+                    }
+                }
+            }
+        }
+
+        match best {
+            Some((_, added, index, position, tile)) => {
+                order.insert(position, remaining.swap_remove(index));
+                tiles.insert(position, tile);
+                current += added;
+            }
+            None if shortened || remaining.is_empty() => break,
+            None => {
+                shortened = true;
+                order = two_opt(legs, order, return_to_source, TWO_OPT_PASSES);
+                (current, tiles) = choose_targets(legs, &order, return_to_source);
+            }
+        }
+    }
+
+    order
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::{conditions, map, Rng};
+    use crate::utils::manhattan_distance;
+
+    /// legs returns the legs measured with the Manhattan distance between the source and the targets of every stop.
+    fn legs(source: (usize, usize), stops: &[Vec<(usize, usize)>]) -> Legs {
+        let mut tiles = vec![source];
+        let mut tiles_stops = vec![None];
+        let mut targets = Vec::new();
+
+        for (stop, stop_targets) in stops.iter().enumerate() {
+            targets.push((tiles.len()..tiles.len() + stop_targets.len()).collect());
+            tiles_stops.extend(stop_targets.iter().map(|_| Some(stop)));
+            tiles.extend(stop_targets.iter().copied());
+        }
+
+        let matrix = tiles
+            .iter()
+            .map(|from| {
+                tiles
+                    .iter()
+                    .map(|to| Some(manhattan_distance(*from, *to)))
+                    .collect()
+            })
+            .collect();
+
+        Legs {
+            tiles,
+            stops: tiles_stops,
+            targets,
+            matrix,
+        }
+    }
+
+    /// brute_force returns the smallest measure visiting the remaining stops from the current tile, trying every order and target.
+    fn brute_force(
+        legs: &Legs,
+        remaining: &[usize],
+        current: usize,
+        return_to_source: bool,
+    ) -> usize {
+        if remaining.is_empty() {
+            return match return_to_source {
+                true => legs.measure(current, 0),
+                false => 0,
+            };
+        }
+
+        remaining
+            .iter()
+            .flat_map(|stop| legs.targets[*stop].iter().map(move |tile| (*stop, *tile)))
+            .map(|(stop, tile)| {
+                let others: Vec<usize> = remaining.iter().copied().filter(|s| *s != stop).collect();
+                legs.measure(current, tile) + brute_force(legs, &others, tile, return_to_source)
+            })
+            .min()
+            .unwrap()
+    }
+
+    /// fixed_order returns the smallest measure visiting the stops in the given order from the current tile, trying every target.
+    fn fixed_order(legs: &Legs, order: &[usize], current: usize, return_to_source: bool) -> usize {
+        match order.split_first() {
+            None if return_to_source => legs.measure(current, 0),
+            None => 0,
+            Some((stop, others)) => legs.targets[*stop]
+                .iter()
+                .map(|tile| {
+                    legs.measure(current, *tile)
+                        + fixed_order(legs, others, *tile, return_to_source)
+                })
+                .min()
+                .unwrap(),
+        }
+    }
+
+    fn tiles_measure(legs: &Legs, tiles: &[usize]) -> usize {
+        std::iter::once(0)
+            .chain(tiles.iter().copied())
+            .zip(tiles.iter())
+            .map(|(from, to)| legs.measure(from, *to))
+            .sum()
+    }
+
+    /// random_legs returns the legs of up to the given number of stops, each one with up to 3 targets.
+    fn random_legs(rng: &mut Rng, stops: usize) -> Legs {
+        let source = (rng.next(10), rng.next(10));
+        let stops: Vec<Vec<(usize, usize)>> = (0..1 + rng.next(stops))
+            .map(|_| {
+                (0..1 + rng.next(3))
+                    .map(|_| (rng.next(10), rng.next(10)))
+                    .collect()
+            })
+            .collect();
+
+        legs(source, &stops)
+    }
+
+    #[test]
+    fn held_karp_and_reconstruct_find_the_shortest_tour_over_the_targets() {
+        let mut rng = Rng::new(9);
+
+        for _ in 0..50 {
+            let legs = random_legs(&mut rng, 5);
+            let stops: Vec<usize> = (0..legs.targets.len()).collect();
+
+            let held_karp = held_karp(&legs, &stops);
+            let full = (1 << stops.len()) - 1;
+            let last = (1..legs.tiles.len())
+                .min_by_key(|last| held_karp.costs[full][*last])
+                .unwrap();
+
+            assert_eq!(
+                held_karp.costs[full][last],
+                brute_force(&legs, &stops, 0, false)
+            );
+
+            let tiles = reconstruct(&held_karp, full, last);
+            let mut visited: Vec<usize> = tiles
+                .iter()
+                .map(|tile| legs.stops[*tile].unwrap())
+                .collect();
+            visited.sort();
+
+            assert_eq!(visited, stops);
+            assert_eq!(tiles.last(), Some(&last));
+            assert_eq!(tiles_measure(&legs, &tiles), held_karp.costs[full][last]);
+        }
+    }
+
+    #[test]
+    fn reconstruct_follows_the_previous_tiles() {
+        let legs = legs((0, 0), &[vec![(0, 3)], vec![(0, 1)], vec![(0, 2)]]);
+        let held_karp = held_karp(&legs, &[0, 1, 2]);
+
+        assert_eq!(reconstruct(&held_karp, 0b111, 1), vec![2, 3, 1]);
+        assert_eq!(reconstruct(&held_karp, 0b011, 1), vec![2, 1]);
+        assert_eq!(reconstruct(&held_karp, 0b001, 1), vec![1]);
+    }
+
+    #[test]
+    fn choose_targets_picks_the_best_target_of_every_stop() {
+        let mut rng = Rng::new(10);
+
+        for _ in 0..50 {
+            let legs = random_legs(&mut rng, 5);
+            let order: Vec<usize> = (0..legs.targets.len()).collect();
+
+            for return_to_source in [false, true] {
+                let (measure, tiles) = choose_targets(&legs, &order, return_to_source);
+
+                let mut expected = tiles_measure(&legs, &tiles);
+                if return_to_source {
+                    expected += legs.measure(*tiles.last().unwrap(), 0);
+                }
+
+                assert_eq!(measure, expected);
+                assert_eq!(measure, fixed_order(&legs, &order, 0, return_to_source));
+                assert!(tiles
+                    .iter()
+                    .zip(order.iter())
+                    .all(|(tile, stop)| legs.stops[*tile] == Some(*stop)));
+            }
+        }
+    }
+
+    #[test]
+    fn two_opt_removes_the_crossings() {
+        let legs = legs(
+            (0, 0),
+            &[vec![(0, 4)], vec![(0, 1)], vec![(0, 3)], vec![(0, 2)]],
+        );
+
+        assert_eq!(order_measure(&legs, &[0, 1, 2, 3], false), 10);
+        assert_eq!(
+            two_opt(&legs, vec![0, 1, 2, 3], false, usize::MAX),
+            vec![1, 3, 2, 0]
+        );
+
+        let mut rng = Rng::new(11);
+
+        for _ in 0..50 {
+            let legs = random_legs(&mut rng, 5);
+            let order: Vec<usize> = (0..legs.targets.len()).collect();
+
+            for return_to_source in [false, true] {
+                let improved = two_opt(&legs, order.clone(), return_to_source, usize::MAX);
+
+                assert!(
+                    order_measure(&legs, &improved, return_to_source)
+                        <= order_measure(&legs, &order, return_to_source)
+                );
+                assert!(
+                    order_measure(&legs, &improved, return_to_source)
+                        >= brute_force(&legs, &order, 0, return_to_source)
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn tour_starts_every_leg_from_the_reached_target() {
+        let environmental_conditions = conditions();
+        let map = map(&[
+            ". . . . . .",
+            ". # . . # .",
+            ". . . . . .",
+            ". . . # . .",
+            ". . . . . .",
+            ". . . . . .",
+        ]);
+        let source = (5, 0);
+        let destinations = [((1, 1), 1), ((1, 4), 1), ((3, 3), 1)];
+        let options = SearchOptions::default();
+
+        let stops = get_stops(&map, true, &destinations);
+        let tour = tour(
+            &environmental_conditions,
+            &map,
+            source,
+            get_stops(&map, true, &destinations),
+            &options,
+        )
+        .unwrap();
+
+        assert_eq!(tour.paths.len(), 3);
+        assert_eq!(tour.paths[0].source, source);
+        assert!(tour
+            .paths
+            .windows(2)
+            .all(|paths| paths[1].source == paths[0].destination));
+        assert_eq!(
+            tour.cost,
+            tour.paths.iter().map(|path| path.cost).sum::<usize>()
+        );
+
+        // every order of the stops and every choice of their targets, with the actual paths
+        let cost = |from: (usize, usize), to: (usize, usize)| {
+            dijkstra(
+                &environmental_conditions,
+                &map,
+                from,
+                HashSet::from([to]),
+                &options,
+            )
+            .unwrap()
+            .cost
+        };
+        let mut best = usize::MAX;
+
+        for order in [
+            [0, 1, 2],
+            [0, 2, 1],
+            [1, 0, 2],
+            [1, 2, 0],
+            [2, 0, 1],
+            [2, 1, 0],
+        ] {
+            for first in stops[order[0]].targets.iter() {
+                for second in stops[order[1]].targets.iter() {
+                    for third in stops[order[2]].targets.iter() {
+                        best = best.min(
+                            cost(source, *first) + cost(*first, *second) + cost(*second, *third),
+                        );
+                    }
+                }
+            }
+        }
+
+        assert_eq!(tour.cost, best);
+    }
+
+    #[test]
+    fn solve_budgeted_exact_finds_the_most_valuable_subset() {
+        let mut rng = Rng::new(12);
+
+        for _ in 0..50 {
+            let legs = random_legs(&mut rng, 5);
+            let stops: Vec<usize> = (0..legs.targets.len()).collect();
+            let values: Vec<usize> = stops.iter().map(|_| rng.next(4)).collect();
+            let budget = rng.next(40);
+
+            for return_to_source in [false, true] {
+                let expected = (0..1usize << stops.len())
+                    .map(|mask| -> Vec<usize> {
+                        stops
+                            .iter()
+                            .copied()
+                            .filter(|stop| mask & (1 << stop) != 0)
+                            .collect()
+                    })
+                    .filter(|subset| brute_force(&legs, subset, 0, return_to_source) <= budget)
+                    .map(|subset| subset.iter().map(|stop| values[*stop]).sum::<usize>())
+                    .max()
+                    .unwrap();
+
+                let tiles = solve_budgeted_exact(&legs, &values, &stops, budget, return_to_source);
+
+                let mut measure = tiles_measure(&legs, &tiles);
+                if return_to_source && !tiles.is_empty() {
+                    measure += legs.measure(*tiles.last().unwrap(), 0);
+                }
+
+                assert!(measure <= budget);
+                assert_eq!(
+                    tiles
+                        .iter()
+                        .map(|tile| values[legs.stops[*tile].unwrap()])
+                        .sum::<usize>(),
+                    expected
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn solve_budgeted_insertion_fits_the_budget() {
+        let legs = legs(
+            (0, 0),
+            &[vec![(0, 2)], vec![(0, 9)], vec![(0, 1)], vec![(0, 3)]],
+        );
+
+        assert_eq!(
+            solve_budgeted_insertion(&legs, &[1, 1, 1, 1], &[0, 1, 2, 3], 3, false).len(),
+            3
+        );
+        assert_eq!(
+            solve_budgeted_insertion(&legs, &[1, 1, 1, 1], &[0, 1, 2, 3], 6, true).len(),
+            3
+        );
+
+        let mut rng = Rng::new(13);
+
+        for _ in 0..50 {
+            let legs = random_legs(&mut rng, 20);
+            let stops: Vec<usize> = (0..legs.targets.len()).collect();
+            let values: Vec<usize> = stops.iter().map(|_| rng.next(4)).collect();
+            let valuable = values.iter().filter(|value| **value > 0).count();
+            let budget = rng.next(80);
+
+            for return_to_source in [false, true] {
+                let order =
+                    solve_budgeted_insertion(&legs, &values, &stops, budget, return_to_source);
+
+                let mut visited = order.clone();
+                visited.sort();
+                visited.dedup();
+
+                assert!(order_measure(&legs, &order, return_to_source) <= budget);
+                assert_eq!(visited.len(), order.len());
+                assert!(order.iter().all(|stop| values[*stop] > 0));
+
+                let order =
+                    solve_budgeted_insertion(&legs, &values, &stops, usize::MAX, return_to_source);
+                assert_eq!(order.len(), valuable);
+            }
+        }
+    }
+}
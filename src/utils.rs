@@ -1,9 +1,13 @@
-use robotics_lib::interface::{look_at_sky, Direction};
-use robotics_lib::runner::Runnable;
+use std::collections::{HashSet, VecDeque};
+
+use robotics_lib::interface::Direction;
 use robotics_lib::utils::calculate_cost_go_with_environment;
-use robotics_lib::world::{tile::Tile, tile::TileType, World};
+use robotics_lib::world::environmental_conditions::EnvironmentalConditions;
+use robotics_lib::world::{tile::Tile, tile::TileType};
 
-use crate::plain::{PlainContent, PlainTileType};
+use crate::cost::CostModel;
+use crate::error::TomTomError;
+use crate::path::{Action, StepCost};
 
 fn get_coords_row_col(source: (usize, usize), direction: Direction) -> (usize, usize) {
     let (row, col) = source;
@@ -16,8 +20,24 @@ fn get_coords_row_col(source: (usize, usize), direction: Direction) -> (usize, u
     }
 }
 
+pub(crate) fn get_next_coords(
+    size: usize,
+    source: (usize, usize),
+    direction: &Direction,
+) -> Option<(usize, usize)> {
+    let (row, col) = source;
+
+    match direction {
+        Direction::Up if row > 0 => Some((row - 1, col)),
+        Direction::Down if row + 1 < size => Some((row + 1, col)),
+        Direction::Left if col > 0 => Some((row, col - 1)),
+        Direction::Right if col + 1 < size => Some((row, col + 1)),
+        _ => None,
+    }
+}
+
 pub(crate) fn get_adjacent_tiles(
-    map: &Vec<Vec<Option<Tile>>>,
+    map: &[Vec<Option<Tile>>],
     tile: (usize, usize),
 ) -> Vec<(usize, usize)> {
     let mut ret = Vec::new();
@@ -44,36 +64,19 @@ pub(crate) fn get_adjacent_tiles(
     ret
 }
 
-pub(crate) fn get_specific_tiles(
-    map: &Vec<Vec<Option<Tile>>>,
-    plain_tile_type: &Option<PlainTileType>,
-    plain_content: &Option<PlainContent>,
-) -> Vec<(usize, usize)> {
-    let mut ret = Vec::new();
-
-    let size = map.len();
-
-    for row in 0..size {
-        for col in 0..size {
-            if let Some(tile) = map[row][col].as_ref() {
-                let mut control = true;
-
-                if let Some(plain_tile_type) = plain_tile_type {
-                    if !plain_tile_type.eq_tile_type(&tile.tile_type) {
-                        control = false;
-                    }
-                }
-
-                if let Some(plain_content) = plain_content {
-                    if !plain_content.eq_content(&tile.content) {
-                        control = false;
-                    }
-                }
-
-                if control {
-                    ret.push((row, col));
-                }
-            }
+/// get_targets returns the destination tiles, or their adjacent tiles if adjacent is true.
+pub(crate) fn get_targets(
+    map: &[Vec<Option<Tile>>],
+    adjacent: bool,
+    destinations: impl IntoIterator<Item = (usize, usize)>,
+) -> HashSet<(usize, usize)> {
+    let mut ret = HashSet::new();
+
+    for destination in destinations {
+        if adjacent {
+            ret.extend(get_adjacent_tiles(map, destination));
+        } else {
+            ret.insert(destination);
         }
     }
 
@@ -81,85 +84,207 @@ pub(crate) fn get_specific_tiles(
 }
 
 pub(crate) fn calculate_go_cost(
-    world: &World,
-    map: &Vec<Vec<Option<Tile>>>,
+    environmental_conditions: &EnvironmentalConditions,
+    map: &[Vec<Option<Tile>>],
+    source: (usize, usize),
+    direction: Direction,
+) -> Result<usize, TomTomError> {
+    calculate_go_step_cost(environmental_conditions, map, source, direction).map(|step| step.cost)
+}
+
+pub(crate) fn calculate_go_step_cost(
+    environmental_conditions: &EnvironmentalConditions,
+    map: &[Vec<Option<Tile>>],
     source: (usize, usize),
     direction: Direction,
-) -> Result<usize, String> {
+) -> Result<StepCost, TomTomError> {
     let (source_row, source_col) = source;
     let (destination_row, destination_col) = get_coords_row_col(source, direction);
 
     if map[source_row][source_col].is_none() {
-        return Err(String::from("Source is None!"));
+        return Err(TomTomError::Undiscovered((source_row, source_col)));
     }
 
     if map[destination_row][destination_col].is_none() {
-        return Err(String::from("Destination is None!"));
+        return Err(TomTomError::Undiscovered((
+            destination_row,
+            destination_col,
+        )));
     }
 
     let source = map[source_row][source_col].clone().unwrap();
     let destination = map[destination_row][destination_col].clone().unwrap();
 
     if !destination.tile_type.properties().walk() {
-        return Err(String::from("Go not allowed!"));
+        return Err(TomTomError::NotWalkable((destination_row, destination_col)));
     }
 
-    let mut base_cost = destination.tile_type.properties().cost();
+    let base_cost = destination.tile_type.properties().cost();
     let mut elevation_cost = 0;
 
-    base_cost =
-        calculate_cost_go_with_environment(base_cost, look_at_sky(world), destination.tile_type);
+    let environment_cost = calculate_cost_go_with_environment(
+        base_cost,
+        environmental_conditions.clone(),
+        destination.tile_type,
+    );
 
     if destination.elevation > source.elevation {
         elevation_cost = (destination.elevation - source.elevation).pow(2);
     }
 
-    Ok(base_cost + elevation_cost)
+    Ok(StepCost {
+        coordinates: (destination_row, destination_col),
+        base_cost,
+        environment_cost,
+        elevation_cost,
+        teleport_cost: 0,
+        cost: environment_cost + elevation_cost,
+        unknown: false,
+    })
+}
+
+pub(crate) fn calculate_min_go_cost(environmental_conditions: &EnvironmentalConditions) -> usize {
+    let tile_types = [
+        TileType::DeepWater,
+        TileType::ShallowWater,
+        TileType::Sand,
+        TileType::Grass,
+        TileType::Street,
+        TileType::Hill,
+        TileType::Mountain,
+        TileType::Snow,
+        TileType::Lava,
+        TileType::Teleport(true),
+        TileType::Teleport(false),
+        TileType::Wall,
+    ];
+
+    tile_types
+        .into_iter()
+        .filter(|tile_type| tile_type.properties().walk())
+        .map(|tile_type| {
+            calculate_cost_go_with_environment(
+                tile_type.properties().cost(),
+                environmental_conditions.clone(),
+                tile_type,
+            )
+        })
+        .min()
+        .unwrap_or(0)
+}
+
+pub(crate) fn manhattan_distance(a: (usize, usize), b: (usize, usize)) -> usize {
+    a.0.abs_diff(b.0) + a.1.abs_diff(b.1)
 }
 
 pub(crate) fn calculate_teleport_cost(
-    robot: &impl Runnable,
-    map: &Vec<Vec<Option<Tile>>>,
+    map: &[Vec<Option<Tile>>],
+    source: (usize, usize),
     destination: (usize, usize),
-) -> Result<usize, String> {
-    let (source_row, source_col) = (
-        robot.get_coordinate().get_row(),
-        robot.get_coordinate().get_col(),
-    );
+) -> Result<usize, TomTomError> {
+    let (source_row, source_col) = (source.0, source.1);
 
     let (destination_row, destination_col) = (destination.0, destination.1);
 
     let size = map.len();
 
     if source_row >= size || source_col >= size {
-        return Err(String::from("Source out of bounds!"));
+        return Err(TomTomError::SourceOutOfBounds);
     }
 
     if destination_row >= size || destination_col >= size {
-        return Err(String::from("Destination out of bounds!"));
+        return Err(TomTomError::OutOfBounds((destination_row, destination_col)));
     }
 
     match &map[source_row][source_col] {
         None => {
-            return Err(String::from("Source is None!"));
+            return Err(TomTomError::Undiscovered((source_row, source_col)));
         }
         Some(tile) => {
             if tile.tile_type != TileType::Teleport(true) {
-                return Err(String::from("Source is not a teleport!"));
+                return Err(TomTomError::NotATeleport((source_row, source_col)));
             }
         }
     }
 
     match &map[destination_row][destination_col] {
         None => {
-            return Err(String::from("Destination is None!"));
+            return Err(TomTomError::Undiscovered((
+                destination_row,
+                destination_col,
+            )));
         }
         Some(tile) => {
             if tile.tile_type != TileType::Teleport(true) {
-                return Err(String::from("Destination is not a teleport!"));
+                return Err(TomTomError::NotATeleport((
+                    destination_row,
+                    destination_col,
+                )));
             }
         }
     }
 
     Ok(30)
 }
+
+pub(crate) fn get_action_destination(
+    size: usize,
+    source: (usize, usize),
+    action: &Action,
+) -> Option<(usize, usize)> {
+    match action {
+        Action::Go(direction) => get_next_coords(size, source, direction),
+        Action::Teleport(destination) => Some(*destination),
+    }
+}
+
+pub(crate) fn calculate_action_cost(
+    cost_model: &dyn CostModel,
+    environmental_conditions: &EnvironmentalConditions,
+    map: &[Vec<Option<Tile>>],
+    source: (usize, usize),
+    action: &Action,
+) -> Result<usize, TomTomError> {
+    match action {
+        Action::Go(direction) => {
+            if get_next_coords(map.len(), source, direction).is_none() {
+                return Err(TomTomError::OutOfBounds(source));
+            }
+
+            cost_model.go_cost(map, environmental_conditions, source, direction.clone())
+        }
+        Action::Teleport(destination) => cost_model.teleport_cost(map, source, *destination),
+    }
+}
+
+pub(crate) fn calculate_actions_costs(
+    cost_model: &dyn CostModel,
+    environmental_conditions: &EnvironmentalConditions,
+    map: &[Vec<Option<Tile>>],
+    source: (usize, usize),
+    actions: &VecDeque<Action>,
+) -> Vec<Option<usize>> {
+    let mut ret = Vec::new();
+    let mut position = Some(source);
+
+    for action in actions.iter() {
+        match position {
+            None => ret.push(None),
+            Some(source) => {
+                ret.push(
+                    calculate_action_cost(
+                        cost_model,
+                        environmental_conditions,
+                        map,
+                        source,
+                        action,
+                    )
+                    .ok(),
+                );
+                position = get_action_destination(map.len(), source, action);
+            }
+        }
+    }
+
+    ret
+}
@@ -0,0 +1,280 @@
+use robotics_lib::world::environmental_conditions::EnvironmentalConditions;
+use robotics_lib::world::tile::Tile;
+
+use crate::error::TomTomError;
+use crate::path::{Action, Path, StepCost};
+use crate::search::SearchOptions;
+use crate::utils::{calculate_go_step_cost, calculate_teleport_cost, get_next_coords};
+
+/// Validation describes a path simulated against the current robot map, before performing it.
+///
+/// # Fields:
+/// - cost: usize => energy cost of the valid actions according to the cost model, equal to the cost of the whole path if it is valid.
+/// - breakdown: Vec<StepCost> => cost of every valid action, in the same order as the actions of the path.
+/// - invalid_step: Option<usize> => index of the first action that can't be performed, None if the path is valid.
+/// - failure: Option<TomTomError> => reason why the action of index invalid_step can't be performed.
+#[derive(Debug)]
+pub struct Validation {
+    pub cost: usize,
+    pub breakdown: Vec<StepCost>,
+    pub invalid_step: Option<usize>,
+    pub failure: Option<TomTomError>,
+}
+
+impl Validation {
+    /// is_valid returns true if every action of the path can be performed.
+    pub fn is_valid(&self) -> bool {
+        self.failure.is_none()
+    }
+}
+
+/// validate simulates the actions of the path from the robot's position with the given environmental conditions, stopping at the first action
+/// leaving the map, entering an undiscovered or not walkable tile, teleporting between inactive teleports or exceeding the available energy.
+pub(crate) fn validate(
+    mut position: (usize, usize),
+    available: usize,
+    environmental_conditions: &EnvironmentalConditions,
+    map: &[Vec<Option<Tile>>],
+    path: &Path,
+    options: &SearchOptions,
+) -> Validation {
+    let mut validation = Validation {
+        cost: 0,
+        breakdown: Vec::new(),
+        invalid_step: None,
+        failure: None,
+    };
+
+    if position != path.source {
+        validation.invalid_step = Some(0);
+        validation.failure = Some(TomTomError::NotOnSource(position));
+        return validation;
+    }
+
+    let mut required = 0;
+
+    for (step, action) in path.actions.iter().enumerate() {
+        let step_cost = match action {
+            Action::Go(direction) => match get_next_coords(map.len(), position, direction) {
+                None => Err(TomTomError::OutOfBounds(position)),
+                Some(_) => calculate_go_step_cost(
+                    environmental_conditions,
+                    map,
+                    position,
+                    direction.clone(),
+                )
+                .and_then(|step_cost| {
+                    options
+                        .cost_model
+                        .go_cost(map, environmental_conditions, position, direction.clone())
+                        .map(|cost| StepCost { cost, ..step_cost })
+                }),
+            },
+            Action::Teleport(destination) => calculate_teleport_cost(map, position, *destination)
+                .and_then(|teleport_cost| {
+                    options
+                        .cost_model
+                        .teleport_cost(map, position, *destination)
+                        .map(|cost| StepCost {
+                            coordinates: *destination,
+                            teleport_cost,
+                            cost,
+                            ..StepCost::default()
+                        })
+                }),
+        };
+
+        let step_cost = match step_cost {
+            Ok(step_cost) => step_cost,
+            Err(e) => {
+                validation.invalid_step = Some(step);
+                validation.failure = Some(e);
+                break;
+            }
+        };
+
+        required += step_cost.environment_cost + step_cost.elevation_cost + step_cost.teleport_cost;
+
+        if required > available {
+            validation.invalid_step = Some(step);
+            validation.failure = Some(TomTomError::NotEnoughEnergy {
+                required,
+                available,
+            });
+            break;
+        }
+
+        position = step_cost.coordinates;
+        validation.cost += step_cost.cost;
+        validation.breakdown.push(step_cost);
+    }
+
+    validation
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use robotics_lib::interface::Direction;
+
+    use super::*;
+    use crate::dijkstra::dijkstra;
+    use crate::test_utils::{conditions, map};
+
+    fn path(source: (usize, usize), actions: &[Action]) -> Path {
+        let mut path = Path::new(source, source, 0);
+        path.actions = actions.iter().cloned().collect();
+        path
+    }
+
+    fn run(position: (usize, usize), energy: usize, rows: &[&str], path: &Path) -> Validation {
+        validate(
+            position,
+            energy,
+            &conditions(),
+            &map(rows),
+            path,
+            &SearchOptions::default(),
+        )
+    }
+
+    #[test]
+    fn recomputes_the_cost_on_the_current_map() {
+        let searched = [". . .", ". . .", ". . ."];
+        let path = dijkstra(
+            &conditions(),
+            &map(&searched),
+            (0, 0),
+            HashSet::from([(0, 2)]),
+            &SearchOptions::default(),
+        )
+        .unwrap();
+
+        let validation = run((0, 0), 1000, &searched, &path);
+        assert!(validation.is_valid());
+        assert_eq!(validation.cost, path.cost);
+        assert_eq!(validation.breakdown, path.breakdown);
+
+        let validation = run((0, 0), 1000, &[". : .", ". . .", ". . ."], &path);
+        assert!(validation.is_valid());
+        assert!(validation.cost > path.cost);
+        assert_eq!(
+            validation
+                .breakdown
+                .iter()
+                .map(|step| step.cost)
+                .sum::<usize>(),
+            validation.cost
+        );
+    }
+
+    #[test]
+    fn fails_when_the_robot_is_not_on_the_source() {
+        let validation = run(
+            (1, 1),
+            1000,
+            &[". . .", ". . .", ". . ."],
+            &path((0, 0), &[Action::Go(Direction::Right)]),
+        );
+
+        assert_eq!(validation.invalid_step, Some(0));
+        assert!(matches!(
+            validation.failure,
+            Some(TomTomError::NotOnSource((1, 1)))
+        ));
+    }
+
+    #[test]
+    fn fails_when_an_action_leaves_the_map() {
+        let validation = run(
+            (0, 0),
+            1000,
+            &[". . .", ". . .", ". . ."],
+            &path(
+                (0, 0),
+                &[Action::Go(Direction::Right), Action::Go(Direction::Up)],
+            ),
+        );
+
+        assert_eq!(validation.invalid_step, Some(1));
+        assert!(matches!(
+            validation.failure,
+            Some(TomTomError::OutOfBounds((0, 1)))
+        ));
+        assert_eq!(validation.breakdown.len(), 1);
+        assert_eq!(validation.cost, validation.breakdown[0].cost);
+    }
+
+    #[test]
+    fn fails_when_an_action_walks_into_a_wall() {
+        let validation = run(
+            (0, 0),
+            1000,
+            &[". . .", ". # .", ". . ."],
+            &path(
+                (0, 0),
+                &[Action::Go(Direction::Right), Action::Go(Direction::Down)],
+            ),
+        );
+
+        assert_eq!(validation.invalid_step, Some(1));
+        assert!(matches!(
+            validation.failure,
+            Some(TomTomError::NotWalkable((1, 1)))
+        ));
+    }
+
+    #[test]
+    fn fails_when_a_teleport_is_inactive() {
+        let validation = run(
+            (0, 0),
+            1000,
+            &["O . o", ". . .", ". . O"],
+            &path((0, 0), &[Action::Teleport((0, 2))]),
+        );
+
+        assert_eq!(validation.invalid_step, Some(0));
+        assert!(matches!(
+            validation.failure,
+            Some(TomTomError::NotATeleport((0, 2)))
+        ));
+
+        let validation = run(
+            (0, 0),
+            1000,
+            &["O . o", ". . .", ". . O"],
+            &path((0, 0), &[Action::Teleport((2, 2))]),
+        );
+
+        assert!(validation.is_valid());
+        assert_eq!(validation.breakdown[0].coordinates, (2, 2));
+    }
+
+    #[test]
+    fn fails_when_the_energy_runs_out() {
+        let validation = run(
+            (0, 0),
+            2,
+            &[". . . .", ". . . .", ". . . .", ". . . ."],
+            &path(
+                (0, 0),
+                &[
+                    Action::Go(Direction::Right),
+                    Action::Go(Direction::Right),
+                    Action::Go(Direction::Right),
+                ],
+            ),
+        );
+
+        assert_eq!(validation.invalid_step, Some(2));
+        assert!(matches!(
+            validation.failure,
+            Some(TomTomError::NotEnoughEnergy {
+                required: 3,
+                available: 2
+            })
+        ));
+        assert_eq!(validation.cost, 2);
+    }
+}